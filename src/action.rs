@@ -1,28 +1,37 @@
-use crate::context::Coord;
+use crate::context::{Coord, TimeCat, MAX_WAIT};
 
 use std::fmt::{Display, Error, Formatter};
+use std::str::FromStr;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 #[must_use]
 pub enum Action {
     Describe,
-    Go,
-    Wait,
+    Go(Direction),
+    GoRelative(Relative),
+    Wait(Span),
+    WaitUntil(Condition),
+    Sleep,
+    ReadBarometer,
+    Save,
+    Load,
     Quit,
 }
 
 impl Display for Action {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
-        write!(
-            f,
-            "{}",
-            match self {
-                Self::Describe => "describe",
-                Self::Go => "go",
-                Self::Wait => "wait",
-                Self::Quit => "quit",
-            }
-        )
+        match self {
+            Self::Describe => write!(f, "describe"),
+            Self::Go(direction) => write!(f, "go {}", direction),
+            Self::GoRelative(relative) => write!(f, "go {}", relative),
+            Self::Wait(span) => write!(f, "wait {}", span),
+            Self::WaitUntil(condition) => write!(f, "wait for {}", condition),
+            Self::Sleep => write!(f, "sleep"),
+            Self::ReadBarometer => write!(f, "read the barometer"),
+            Self::Save => write!(f, "save"),
+            Self::Load => write!(f, "load"),
+            Self::Quit => write!(f, "quit"),
+        }
     }
 }
 
@@ -45,6 +54,29 @@ impl Direction {
         };
         Coord { n, w }
     }
+
+    pub const fn opposite(&self) -> Self {
+        match self {
+            Self::North => Self::South,
+            Self::South => Self::North,
+            Self::East => Self::West,
+            Self::West => Self::East,
+        }
+    }
+
+    // the direction on your left when facing this way
+    pub const fn left(&self) -> Self {
+        match self {
+            Self::North => Self::West,
+            Self::West => Self::South,
+            Self::South => Self::East,
+            Self::East => Self::North,
+        }
+    }
+
+    pub const fn right(&self) -> Self {
+        self.left().opposite()
+    }
 }
 
 impl Display for Direction {
@@ -61,3 +93,209 @@ impl Display for Direction {
         )
     }
 }
+
+// a direction relative to the local slope rather than the compass
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[must_use]
+pub enum Relative {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Relative {
+    // pick the compass direction given which way is uphill
+    pub const fn resolve(&self, uphill: Direction) -> Direction {
+        match self {
+            Self::Up => uphill,
+            Self::Down => uphill.opposite(),
+            Self::Left => uphill.left(),
+            Self::Right => uphill.right(),
+        }
+    }
+}
+
+impl Display for Relative {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Up => "up the slope",
+                Self::Down => "down the slope",
+                Self::Left => "left of the upward slope",
+                Self::Right => "right of the upward slope",
+            }
+        )
+    }
+}
+
+// a length of in-game time
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Span {
+    mins: u32,
+}
+
+impl Span {
+    pub const fn from_mins(mins: u32) -> Self {
+        Self { mins }
+    }
+
+    pub const fn from_hours(hours: u32) -> Self {
+        Self { mins: hours * 60 }
+    }
+
+    pub const fn mins(&self) -> u32 {
+        self.mins
+    }
+}
+
+impl Display for Span {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        let (days, hours, mins) = (self.mins / 1440, self.mins / 60 % 24, self.mins % 60);
+        let mut parts = Vec::new();
+        for (amount, unit) in [(days, "day"), (hours, "hour"), (mins, "minute")] {
+            match amount {
+                0 => (),
+                1 => parts.push(format!("1 {}", unit)),
+                _ => parts.push(format!("{} {}s", amount, unit)),
+            }
+        }
+
+        if parts.is_empty() {
+            write!(f, "no time")
+        } else {
+            write!(f, "{}", parts.join(" "))
+        }
+    }
+}
+
+// accepts things like "20 minutes", "3 hours", "1h30m" or a bare number of
+// minutes, from a minute up to MAX_WAIT
+impl FromStr for Span {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        let mut total: u32 = 0;
+        let mut rest = s.as_str();
+        if let Ok(mins) = s.parse::<u32>() {
+            total = mins;
+            rest = "";
+        }
+
+        while !rest.is_empty() {
+            let digits = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len());
+            let amount: u32 = rest[..digits]
+                .parse()
+                .map_err(|_| format!("expected a number at '{}'", rest))?;
+            rest = rest[digits..].trim_start();
+
+            let letters = rest
+                .find(|c: char| !c.is_ascii_alphabetic())
+                .unwrap_or(rest.len());
+            let per_unit = match &rest[..letters] {
+                "m" | "min" | "mins" | "minute" | "minutes" => 1,
+                "h" | "hr" | "hrs" | "hour" | "hours" => 60,
+                "d" | "day" | "days" => 1440,
+                "" => return Err("missing a unit (minutes, hours or days)".to_string()),
+                unit => return Err(format!("unknown unit '{}'", unit)),
+            };
+            rest = rest[letters..].trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+            rest = rest.strip_prefix("and ").unwrap_or(rest);
+
+            total = amount
+                .checked_mul(per_unit)
+                .and_then(|mins| total.checked_add(mins))
+                .ok_or_else(|| "that's far too long".to_string())?;
+        }
+
+        if total == 0 {
+            Err("no time given".to_string())
+        } else if total > MAX_WAIT.mins() {
+            Err(format!("you can wait at most {}", MAX_WAIT))
+        } else {
+            Ok(Self::from_mins(total))
+        }
+    }
+}
+
+// something worth waiting for
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[must_use]
+pub enum Condition {
+    Time(TimeCat),
+    Dry,  // it stops raining
+    Calm, // the wind dies down
+}
+
+impl Display for Condition {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Time(TimeCat::Dawn) => "dawn",
+                Self::Time(TimeCat::Morning) => "morning",
+                Self::Time(TimeCat::Noon) => "noon",
+                Self::Time(TimeCat::Afternoon) => "the afternoon",
+                Self::Time(TimeCat::Dusk) => "dusk",
+                Self::Time(TimeCat::Night) => "nightfall",
+                Self::Dry => "the rain to stop",
+                Self::Calm => "the wind to die down",
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_spans() {
+        let parse = |s: &str| s.parse::<Span>().map(|span| span.mins());
+        assert_eq!(parse("20"), Ok(20));
+        assert_eq!(parse(" 20 minutes "), Ok(20));
+        assert_eq!(parse("3 hours"), Ok(180));
+        assert_eq!(parse("1h30m"), Ok(90));
+        assert_eq!(parse("1 day, 2 hours and 5 mins"), Ok(1565));
+        assert_eq!(parse("2 Days"), Ok(2880));
+    }
+
+    #[test]
+    fn rejects_bad_spans() {
+        for s in [
+            "",
+            "0",
+            "0 minutes",
+            "0h0m",
+            "ten minutes",
+            "5 fortnights",
+            "h",
+        ] {
+            assert!(s.parse::<Span>().is_err(), "{:?} parsed", s);
+        }
+    }
+
+    #[test]
+    fn caps_spans_at_the_longest_wait() {
+        assert_eq!(
+            format!("{} minutes", MAX_WAIT.mins()).parse::<Span>(),
+            Ok(MAX_WAIT)
+        );
+        for s in ["4 days", "4321", "99999999999 days"] {
+            assert!(s.parse::<Span>().is_err(), "{:?} parsed", s);
+        }
+    }
+
+    #[test]
+    fn displays_spans() {
+        assert_eq!(Span::from_mins(1).to_string(), "1 minute");
+        assert_eq!(Span::from_mins(1565).to_string(), "1 day 2 hours 5 minutes");
+        assert_eq!(Span::from_mins(0).to_string(), "no time");
+    }
+}
@@ -1,132 +1,1025 @@
-use crate::action::{Action, Direction};
-use crate::input;
-
-use nanorand::{ChaCha, Rng};
+use crate::action::{Action, Condition, Direction, Relative, Span};
+use crate::event::{Event, Interruption, Observer};
+use crate::front::{self, Cover, Front};
+use crate::ground::{Ground, Stale, Weathering};
+use crate::player::{Chill, Exposure, Player, Tiredness};
+use crate::precipitation::{Intensity, Kind, Precipitation};
+use crate::pressure::{Dial, Pressure};
+use crate::rng::WorldRng;
+use crate::save::{SaveReader, SaveWriter};
+use crate::schedule::{Process, Schedule};
+use crate::sun::Daylight;
+use crate::terrain::{Slope, Terrain};
+use crate::weather::{Field, Shelter};
+use crate::world::World;
+
+use nanorand::Rng;
 use std::cmp::Ordering;
-use std::f32::consts;
+use std::collections::HashMap;
 use std::fmt::{self, Display, Formatter};
-use std::io::{Error, Write};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Error, Write};
+use std::mem;
 use std::ops::AddAssign;
-
-#[cfg(debug_assertions)]
-const DEBUG: bool = true;
-#[cfg(not(debug_assertions))]
-const DEBUG: bool = false;
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_SAVE_PATH: &str = "description-system.sav";
+pub const DEFAULT_WAIT: Span = Span::from_mins(5);
+// give up waiting for something that isn't coming after this long
+pub const MAX_WAIT: Span = Span::from_hours(3 * 24);
+// minutes for the humidity to get most of the way to the local base
+const HUMIDITY_SETTLE: f32 = 180.0;
+// a change in temperature this big since a wait began cuts it short
+const TEMP_SWING: i16 = 8;
+// how far off showers can be made out, in coords
+const LOOK_AROUND: i8 = 4;
+// how far from the player the ground is kept up to date, in coords
+const GROUND_RANGE: i8 = 4;
+// at most this many fronts are kept track of at once
+const MAX_FRONTS: usize = 4;
+// fronts drifting further than this from the player are forgotten, in coords
+const FRONT_RANGE: f32 = 100.0;
+// the wind mostly blows from here, and drifts back round to it after a shift
+const PREVAILING_WIND: Direction = Direction::West;
 
 #[derive(Debug)]
-pub struct Context<W: Write> {
-    w: W,
-    rng: ChaCha<20>,
+pub struct Context<O: Observer> {
+    observer: O,
+    events: Vec<Event>,
+    save_path: PathBuf,
+    rng: WorldRng,
+    schedule: Schedule,
     last_desc: String,
+    world: World,
     loc: Location,
+    ground: HashMap<Coord, (Location, Ground, Option<Stale>)>, // everywhere we've been
+    heading: Option<Direction>,                                // last direction moved in
+    player: Player,
+    navigation: Navigation,
     time: Time,
-    season: Season,
-    sky: Sky,
-    wind: Wind,
-    temp: i8, // celcius
+    date: Date,
+    latitude: f32,       // degrees north
+    sky: Sky,            // over the region as a whole, see sky_at
+    wind: Wind,          // likewise, see local_wind
+    wind_dir: Direction, // where the wind blows from
+    fronts: Vec<Front>,
+    field: Field,
+    temp: i8,      // celcius, at Terrain::BASE_ELEVATION
+    humidity: f32, // relative, 0 to 1
+    pressure: Pressure,
 }
 
-impl<W: Write> Context<W> {
-    pub fn spawn(write: W) -> Self {
-        let loc = Location::Forest(Coord::new()); // TODO: randomize coords
+impl<O: Observer> Context<O> {
+    // `latitude` is in degrees north, and sets the length of the days and
+    // which way round the seasons go
+    pub fn spawn(observer: O, latitude: f32) -> Self {
+        Self::from_rng(observer, WorldRng::from_entropy(), latitude)
+    }
+
+    // same seed and same actions always give the same world
+    pub fn with_seed(observer: O, seed: u64, latitude: f32) -> Self {
+        Self::from_rng(observer, WorldRng::new(seed), latitude)
+    }
+
+    fn from_rng(observer: O, mut rng: WorldRng, latitude: f32) -> Self {
+        let seed = rng.seed();
+        let latitude = latitude.clamp(-90.0, 90.0);
+        let world = World::new(seed);
+        let loc = world.location(Coord::new()); // TODO: randomize coords
         let time = Time::new(6, 0); // TODO: randomize time
-        let season = Season::Winter;
+        let date = Date::new(1, 0); // midwinter, in the north
         let sky = Sky::Rain;
         let wind = Wind::High;
+        // the world opens in a storm
+        let pressure = Pressure::new(998.0, -0.4);
+        let schedule = Schedule::new(&mut rng, &loc, pressure, date.clock(time));
+        let temp = loc.temp_base(
+            date.season(latitude),
+            Daylight::new(date, latitude).strength(time),
+            sky,
+            Terrain::BASE_ELEVATION,
+        );
+        let lapse = Terrain::lapse(world.terrain().elevation(loc.coord()));
+        let ground = Ground::seasonal(date.season(latitude), temp - lapse as i8, loc.trees());
 
         let mut ctx = Self {
-            w: write,
-            rng: ChaCha::new(),
+            observer,
+            events: Vec::new(),
+            save_path: PathBuf::from(DEFAULT_SAVE_PATH),
+            world,
+            rng,
+            schedule,
             last_desc: String::new(),
             loc,
-            temp: loc.temp_base(season, time, sky),
+            heading: None,
+            player: Player::new(),
+            navigation: Navigation::Compass,
+            ground: HashMap::from([(loc.coord(), (loc, ground, None))]),
+            temp,
             time,
-            season,
+            date,
+            latitude,
             sky,
             wind,
+            wind_dir: PREVAILING_WIND,
+            fronts: Vec::new(),
+            field: Field::new(seed),
+            humidity: loc.humidity_base(sky),
+            pressure,
         };
-        ctx.last_desc = ctx.to_string();
+        ctx.last_desc = ctx.surroundings();
 
         ctx
     }
 
-    pub fn available_actions(&self) -> Vec<Action> {
-        [Action::Describe, Action::Go, Action::Wait, Action::Quit]
+    pub const fn seed(&self) -> u64 {
+        self.rng.seed()
+    }
+
+    pub fn save_path(&self) -> &Path {
+        &self.save_path
+    }
+
+    pub fn set_save_path(&mut self, path: impl Into<PathBuf>) {
+        self.save_path = path.into();
+    }
+
+    pub fn save(&self, w: impl Write) -> Result<(), Error> {
+        let mut save = SaveWriter::new(w)?;
+        save.field("rng", &self.rng)?;
+        save.field("schedule", &self.schedule)?;
+        save.field("loc", &self.loc)?;
+        save.field("heading", &self.heading)?;
+        save.field("player", &self.player)?;
+        save.field("time", &self.time)?;
+        save.field("date", &self.date)?;
+        save.field("latitude", &self.latitude)?;
+        save.field("sky", &self.sky)?;
+        save.field("wind", &self.wind)?;
+        save.field("wind_dir", &self.wind_dir)?;
+        save.field("temp", &self.temp)?;
+        save.field("humidity", &self.humidity)?;
+        save.field("pressure", &self.pressure)?;
+        save.list("fronts", self.fronts.iter())?;
+        save.field("field", &self.field)?;
+        let mut ground: Vec<(Coord, Ground, Option<Stale>)> = self
+            .ground
+            .iter()
+            .map(|(coord, (_, ground, stale))| (*coord, *ground, *stale))
+            .collect();
+        ground.sort_by_key(|(coord, ..)| (coord.n, coord.w));
+        save.list("ground", ground.iter())?;
+        save.text("last_desc", &self.last_desc)?;
+        save.finish()
+    }
+
+    // on error the context is left untouched
+    pub fn load(&mut self, r: impl BufRead) -> Result<(), Error> {
+        let mut save = SaveReader::new(r)?;
+        let rng: WorldRng = save.field("rng")?;
+        let schedule = save.field("schedule")?;
+        let loc: Location = save.field("loc")?;
+        let heading = save.field("heading")?;
+        let player = save.field("player")?;
+        let time = save.field("time")?;
+        let date = save.field("date")?;
+        let latitude = save.field("latitude")?;
+        let sky = save.field("sky")?;
+        let wind = save.field("wind")?;
+        let wind_dir = save.field("wind_dir")?;
+        let temp = save.field("temp")?;
+        let humidity = save.field("humidity")?;
+        let pressure = save.field("pressure")?;
+        let fronts = save.list("fronts")?;
+        let field = save.field("field")?;
+        let ground: Vec<(Coord, Ground, Option<Stale>)> = save.list("ground")?;
+        let last_desc = save.text("last_desc")?;
+
+        self.world = World::new(rng.seed());
+        self.ground = ground
             .into_iter()
-            .collect()
+            .map(|(coord, ground, stale)| (coord, (self.world.location(coord), ground, stale)))
+            .collect();
+        self.rng = rng;
+        self.schedule = schedule;
+        // the biome comes from the world, whatever the save says
+        self.loc = self.world.location(loc.coord());
+        self.heading = heading;
+        self.player = player;
+        self.time = time;
+        self.date = date;
+        self.latitude = latitude;
+        self.sky = sky;
+        self.wind = wind;
+        self.wind_dir = wind_dir;
+        self.temp = temp;
+        self.humidity = humidity;
+        self.pressure = pressure;
+        self.fronts = fronts;
+        self.field = field;
+        self.last_desc = last_desc;
+
+        Ok(())
+    }
+
+    pub const fn player(&self) -> &Player {
+        &self.player
+    }
+
+    pub const fn date(&self) -> Date {
+        self.date
+    }
+
+    pub fn season(&self) -> Season {
+        self.date.season(self.latitude)
+    }
+
+    pub const fn latitude(&self) -> f32 {
+        self.latitude
+    }
+
+    pub fn daylight(&self) -> Daylight {
+        Daylight::new(self.date, self.latitude)
+    }
+
+    // how much sun is reaching the world right now, before canopy and clouds
+    fn sun(&self) -> f32 {
+        self.daylight().strength(self.time)
+    }
+
+    pub fn available_actions(&self) -> Vec<Action> {
+        let mut actions = vec![Action::Describe];
+        actions.extend(
+            self.available_relative_directions()
+                .into_iter()
+                .map(Action::GoRelative),
+        );
+        actions.extend(self.available_directions().into_iter().map(Action::Go));
+        actions.push(Action::Wait(DEFAULT_WAIT));
+        actions.extend(
+            self.available_conditions()
+                .into_iter()
+                .map(Action::WaitUntil),
+        );
+        actions.extend([
+            Action::Sleep,
+            Action::ReadBarometer,
+            Action::Save,
+            Action::Load,
+            Action::Quit,
+        ]);
+        actions
     }
 
+    // the things worth waiting for, which are those that aren't already so
+    pub fn available_conditions(&self) -> Vec<Condition> {
+        [
+            Condition::Time(TimeCat::Dawn),
+            Condition::Time(TimeCat::Morning),
+            Condition::Time(TimeCat::Noon),
+            Condition::Time(TimeCat::Afternoon),
+            Condition::Time(TimeCat::Dusk),
+            Condition::Time(TimeCat::Night),
+            Condition::Dry,
+            Condition::Calm,
+        ]
+        .into_iter()
+        .filter(|condition| !self.holds(*condition))
+        .collect()
+    }
+
+    pub fn holds(&self, condition: Condition) -> bool {
+        match condition {
+            Condition::Time(cat) => self.time.classify(&self.daylight()) == cat,
+            Condition::Dry => self.precipitation().is_none(),
+            Condition::Calm => self.local_wind() <= Wind::Light,
+        }
+    }
+
+    // compass directions, unless the relative ones are on offer instead
     pub fn available_directions(&self) -> Vec<Direction> {
-        let mut dirs = Vec::new();
+        if !self.available_relative_directions().is_empty() {
+            return Vec::new();
+        }
+
+        [
+            Direction::North,
+            Direction::South,
+            Direction::East,
+            Direction::West,
+        ]
+        .into_iter()
+        .filter(|direction| self.can_go(*direction))
+        .collect()
+    }
+
+    // only offered in relative mode, and only if there's a slope to go by
+    pub fn available_relative_directions(&self) -> Vec<Relative> {
+        if self.navigation != Navigation::Relative
+            || self.world.terrain().slope(self.loc.coord()) == Slope::Flat
+        {
+            return Vec::new();
+        }
+
+        [
+            Relative::Up,
+            Relative::Down,
+            Relative::Left,
+            Relative::Right,
+        ]
+        .into_iter()
+        .filter(|relative| self.can_go(self.resolve(*relative)))
+        .collect()
+    }
+
+    pub const fn navigation(&self) -> Navigation {
+        self.navigation
+    }
+
+    // the air temperature here, celcius
+    pub fn temp(&self) -> i8 {
+        self.temp_at(self.loc.coord())
+    }
+
+    // it gets colder the higher up you go
+    fn temp_at(&self, coord: Coord) -> i8 {
+        let lapse = Terrain::lapse(self.world.terrain().elevation(coord));
+        (f32::from(self.temp) - lapse).round() as i8
+    }
+
+    // metres above sea level
+    pub fn elevation(&self) -> f32 {
+        self.world.terrain().elevation(self.loc.coord())
+    }
+
+    // what the temperature feels like, which is what gets described. the real
+    // value is still what drives the weather.
+    pub fn apparent_temp(&self) -> i8 {
+        apparent_temp(
+            f32::from(self.temp()),
+            self.local_wind(),
+            self.precipitation().is_some(),
+        )
+        .round() as i8
+    }
+
+    // where the wind is blowing from
+    pub const fn wind_dir(&self) -> Direction {
+        self.wind_dir
+    }
+
+    // the wind as it is felt here, after the lie of the land has had its say
+    pub fn local_wind(&self) -> Wind {
+        let coord = self.loc.coord();
+        let mut wind = self.shelter_at(coord).wind(self.wind);
+        // and up on the heights there's nothing to break it
+        if self.wind > Wind::None
+            && self.world.terrain().elevation(coord) >= Terrain::EXPOSED_ELEVATION
+        {
+            wind.increase();
+        }
+        wind
+    }
+
+    fn shelter_at(&self, coord: Coord) -> Shelter {
+        let gradient = self.world.terrain().gradient(coord);
+        Shelter::classify(gradient, self.wind, self.wind_dir)
+    }
+
+    pub fn fronts(&self) -> &[Front] {
+        &self.fronts
+    }
+
+    // the front over a coord, if any. where two overlap, the one it's deeper
+    // into wins
+    fn cover_at(&self, coord: Coord) -> Option<Cover> {
+        self.fronts
+            .iter()
+            .filter_map(|front| {
+                let depth = front.distance(coord) / front.radius();
+                front.cover(coord).map(|cover| (cover, depth))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(cover, _)| cover)
+    }
+
+    // the sky right here. the sky field holds the sky over the region as a
+    // whole
+    pub fn sky(&self) -> Sky {
+        self.sky_at(self.loc.coord())
+    }
+
+    pub fn sky_at(&self, coord: Coord) -> Sky {
+        self.field.sky(
+            coord,
+            self.sky,
+            self.cover_at(coord),
+            self.shelter_at(coord),
+        )
+    }
+
+    pub const fn pressure(&self) -> Pressure {
+        self.pressure
+    }
+
+    pub const fn humidity(&self) -> f32 {
+        self.humidity
+    }
+
+    pub fn dew_point(&self) -> f32 {
+        dew_point(f32::from(self.temp()), self.humidity)
+    }
+
+    // what's lying on the ground here
+    pub fn ground(&self) -> Ground {
+        self.ground
+            .get(&self.loc.coord())
+            .map(|(_, ground, _)| *ground)
+            .unwrap_or_default()
+    }
+
+    // what's falling from the sky, if anything
+    pub fn precipitation(&self) -> Option<Precipitation> {
+        self.precipitation_at(self.loc.coord())
+    }
+
+    fn precipitation_at(&self, coord: Coord) -> Option<Precipitation> {
+        Precipitation::classify(
+            self.sky_at(coord),
+            self.temp_at(coord),
+            self.wind,
+            self.season(),
+        )
+    }
+
+    pub fn set_navigation(&mut self, navigation: Navigation) {
+        self.navigation = navigation;
+    }
+
+    // in survival mode the cold can kill, which ends the game
+    pub const fn survival(&self) -> bool {
+        self.player.is_mortal()
+    }
+
+    pub fn set_survival(&mut self, survival: bool) {
+        self.player.set_mortal(survival);
+    }
+
+    // the compass direction a relative direction leads in from here
+    pub fn resolve(&self, relative: Relative) -> Direction {
+        relative.resolve(self.world.terrain().gradient(self.loc.coord()).uphill())
+    }
 
-        dirs.push(Direction::North);
-        dirs.push(Direction::South);
-        dirs.push(Direction::East);
-        dirs.push(Direction::West);
+    // false at the edge of the world
+    fn can_go(&self, direction: Direction) -> bool {
+        let mut coord = self.loc.coord();
+        coord += direction.as_coord_with_magnitude(1);
+        coord != self.loc.coord()
+    }
 
-        dirs
+    // events emitted by the most recent action
+    pub fn events(&self) -> &[Event] {
+        &self.events
     }
 
     pub fn act(&mut self, action: Action) -> Result<bool, Error> {
+        self.events.clear();
+
         match action {
-            Action::Describe => {
-                let description = self.to_string();
-                write!(self.w, "{}", description)?;
-                self.last_desc = description.to_string();
+            Action::Describe => self.describe()?,
+            Action::ReadBarometer => self.emit(Event::BarometerRead {
+                hpa: self.pressure.hpa(),
+                tendency: self.pressure.tendency(),
+            })?,
+
+            Action::Go(direction) => self.go(direction, None)?,
+            Action::GoRelative(relative) => self.go(self.resolve(relative), Some(relative))?,
+
+            Action::Wait(span) => {
+                self.emit(Event::Waited { span })?;
+                if let (after, Some(Stop::Interrupted(cause))) = self.wait(span.mins(), None)? {
+                    self.emit(Event::Interrupted { cause, after })?;
+                }
             }
 
-            Action::Go => {
-                let directions = self.available_directions();
-                match self.loc {
-                    Location::Forest(ref mut coord) => {
-                        writeln!(self.w, "which direction?")?;
-                        let direction = input::menu(&mut self.w, &directions)?;
-                        *coord += direction.as_coord_with_magnitude(1);
-                        writeln!(self.w, "you head {}.", direction)?;
-                        self.time_tick(0, 1)?;
+            Action::WaitUntil(condition) => {
+                self.emit(Event::WaitingUntil { condition })?;
+
+                let (after, stop) = if self.holds(condition) {
+                    (Span::from_mins(0), Some(Stop::Met))
+                } else {
+                    self.wait(MAX_WAIT.mins(), Some(condition))?
+                };
+                match stop {
+                    Some(Stop::Met) => self.emit(Event::ConditionMet { condition, after })?,
+                    Some(Stop::Interrupted(cause)) => {
+                        self.emit(Event::Interrupted { cause, after })?;
                     }
+                    None => self.emit(Event::WaitAbandoned { condition, after })?,
                 }
             }
 
-            Action::Wait => {
-                writeln!(self.w, "some time passes.")?; // TODO: mix up time pass messages
-                self.time_tick(0, 5)?;
+            Action::Sleep if !self.player.can_sleep() => {
+                self.emit(Event::NotTired)?;
+            }
+
+            Action::Sleep => {
+                self.emit(Event::FellAsleep)?;
+                self.player.fall_asleep();
+                let (after, stop) = self.wait(self.player.sleep_needed(), None)?;
+                self.player.wake();
+
+                let cause = match stop {
+                    Some(Stop::Interrupted(cause)) => Some(cause),
+                    _ => None,
+                };
+                self.emit(Event::Woke {
+                    after,
+                    cause,
+                    tiredness: self.player.tiredness(),
+                })?;
+                self.describe()?;
+            }
+
+            Action::Save => {
+                let saved =
+                    File::create(&self.save_path).and_then(|file| self.save(BufWriter::new(file)));
+                match saved {
+                    Ok(()) => self.emit(Event::Saved {
+                        path: self.save_path.clone(),
+                    })?,
+                    Err(err) => self.emit(Event::SaveFailed {
+                        reason: err.to_string(),
+                    })?,
+                }
+            }
+
+            Action::Load => {
+                let loaded =
+                    File::open(&self.save_path).and_then(|file| self.load(BufReader::new(file)));
+                match loaded {
+                    Ok(()) => {
+                        self.emit(Event::Loaded {
+                            path: self.save_path.clone(),
+                        })?;
+                        self.describe()?;
+                    }
+                    Err(err) => self.emit(Event::LoadFailed {
+                        reason: err.to_string(),
+                    })?,
+                }
             }
 
             Action::Quit => return Ok(true),
         }
 
+        if self.player.is_frozen() {
+            self.emit(Event::Froze)?;
+            return Ok(true);
+        }
+
         if self.description_changed() {
-            writeln!(self.w, "your surroundings look different.")?;
-            writeln!(self.w)?;
-            self.act(Action::Describe)?;
+            self.emit(Event::SurroundingsChanged)?;
+            self.describe()?;
         }
 
         Ok(false)
     }
 
-    fn time_tick(&mut self, hours: u8, mins: u8) -> Result<(), Error> {
-        self.time.tick(hours, mins);
-        if DEBUG {
-            writeln!(
-                self.w,
-                "debug: {} {}C ({}C)",
-                self.time,
-                self.temp,
-                self.loc.temp_base(self.season, self.time, self.sky)
-            )?;
-        }
-
-        let total_mins: u64 = (u16::from(hours) * Time::HOUR_MINS + u16::from(mins)).into();
-        let mut new_temp = self.temp;
-        for _ in 0..total_mins {
-            // give temperature chance to change
-            if self.rng.generate_range(0_u32..=100_000) < self.loc.chance_temp_change() {
-                // generate temperature change
+    fn go(&mut self, direction: Direction, relative: Option<Relative>) -> Result<(), Error> {
+        let from = self.loc.coord();
+        let mut to = from;
+        to += direction.as_coord_with_magnitude(1);
+        if to == from {
+            // the edge of the world. no time passes
+            return self.emit(Event::Blocked { direction });
+        }
+        let loc = self.world.location(to);
+        if mem::discriminant(&loc) != mem::discriminant(&self.loc) {
+            // the new biome has its own chances
+            let now = self.clock();
+            self.schedule = Schedule::new(&mut self.rng, &loc, self.pressure, now);
+        }
+        let (sky, falling) = (self.sky(), self.precipitation());
+        // somewhere new gets ground to suit its own height and cover
+        if !self.ground.contains_key(&to) {
+            let ground = Ground::seasonal(self.season(), self.temp_at(to), loc.trees());
+            self.ground.insert(to, (loc, ground, None));
+        }
+        self.leave_ground(from, to);
+        self.loc = loc;
+        self.heading = Some(direction);
+        for coord in around(to, GROUND_RANGE) {
+            self.catch_up(coord);
+        }
+
+        let rise = self.world.terrain().elevation(to) - self.world.terrain().elevation(from);
+        self.emit(Event::Moved {
+            direction,
+            relative,
+            rise,
+        })?;
+        let (from_slope, to_slope) = (
+            self.world.terrain().slope(from),
+            self.world.terrain().slope(to),
+        );
+        if from_slope != to_slope {
+            self.emit(Event::SlopeChanged {
+                from: from_slope,
+                to: to_slope,
+            })?;
+        }
+        self.notice_sky(sky, falling)?;
+
+        let (tiredness, chill) = (self.player.tiredness(), self.player.chill());
+        self.player.travel(rise);
+        self.feel_fatigue(tiredness)?;
+        self.feel_chill(chill)?;
+        self.time_tick(1, None)?;
+
+        Ok(())
+    }
+
+    fn describe(&mut self) -> Result<(), Error> {
+        self.last_desc = self.surroundings();
+        self.emit(Event::Described(self.to_string()))
+    }
+
+    // nothing is narrated while asleep, but front-ends still get the events
+    fn emit(&mut self, event: Event) -> Result<(), Error> {
+        if !self.player.is_asleep() {
+            self.observer.observe(&event)?;
+        }
+        self.events.push(event);
+        Ok(())
+    }
+
+    // minutes since the start of the world
+    pub fn clock(&self) -> u64 {
+        self.date.clock(self.time)
+    }
+
+    // pass time an hour at a time, so that changes are noticed as they happen
+    // rather than all at the end. stops early when the condition comes true or
+    // something notable happens. returns how long it took and why it stopped.
+    fn wait(&mut self, mins: u32, until: Option<Condition>) -> Result<(Span, Option<Stop>), Error> {
+        let watch = Watch {
+            until,
+            sleeping: self.player.is_asleep(),
+            wind: self.wind,
+            temp: self.temp(),
+            chill: self.player.chill(),
+        };
+        let start = self.clock();
+
+        let mut left = mins;
+        while left > 0 {
+            let step = left.min(u32::from(Time::HOUR_MINS));
+            if let Some(stop) = self.time_tick(step, Some(&watch))? {
+                let after = Span::from_mins((self.clock() - start) as u32);
+                return Ok((after, Some(stop)));
+            }
+            left -= step;
+        }
+
+        Ok((Span::from_mins(mins), None))
+    }
+
+    // pass up to `mins` minutes, stopping early if anything `watch` is looking
+    // out for happens.
+    fn time_tick(&mut self, mins: u32, watch: Option<&Watch>) -> Result<Option<Stop>, Error> {
+        let (from, felt) = (self.time, self.apparent_temp());
+        let (tiredness, chill) = (self.player.tiredness(), self.player.chill());
+        let end = self.clock() + u64::from(mins);
+        let mut stop = None;
+
+        'ticking: loop {
+            let (at, process) = self.schedule.next();
+
+            if let Some(watch) = watch {
+                // things can change between weather changes too, so stop to
+                // look around whenever they might have
+                loop {
+                    let look = self.next_look();
+                    if look > at.min(end) {
+                        break;
+                    }
+                    let (was, seen) = (self.time.classify(&self.daylight()), self.events.len());
+                    let redrawn = self.advance_clock(look)?;
+                    stop = self.check(watch, was, &self.events[seen..]);
+                    if stop.is_some() {
+                        break 'ticking;
+                    }
+                    if redrawn {
+                        continue 'ticking;
+                    }
+                }
+            }
+            if at > end {
+                break;
+            }
+
+            let (at, seen) = (at.max(self.clock()), self.events.len());
+            let was = self.time.classify(&self.daylight());
+            // if the sky was drawn again on the way, pick again
+            if !self.advance_clock(at)? {
+                self.fire(process)?;
+                self.schedule
+                    .reschedule(&mut self.rng, &self.loc, self.pressure, process, at);
+            }
+
+            if let Some(watch) = watch {
+                stop = self.check(watch, was, &self.events[seen..]);
+                if stop.is_some() {
+                    break;
+                }
+            }
+        }
+        if stop.is_none() {
+            self.advance_clock(end)?;
+        }
+
+        self.emit(Event::TimePassed {
+            from,
+            to: self.time,
+        })?;
+        // notice temperature changes
+        if self.apparent_temp() != felt {
+            self.emit(Event::TempFelt {
+                from: felt,
+                to: self.apparent_temp(),
+            })?;
+        }
+        self.feel_fatigue(tiredness)?;
+        self.feel_chill(chill)?;
+
+        Ok(stop)
+    }
+
+    // the next time a wait should look around, short of the next weather
+    // change: when the time of day changes, or when the weather has drifted
+    // far enough to be different overhead
+    fn next_look(&self) -> u64 {
+        let daylight = self.daylight();
+        let (now, cat) = (self.time.mins(), self.time.classify(&daylight));
+        // the days draw in or out at midnight, so look again then anyway
+        let midnight = Time::DAY_MINS - now;
+        let change = (1..midnight)
+            .find(|mins| Time::from_mins(now + mins).classify(&daylight) != cat)
+            .unwrap_or(midnight);
+        // coords per hour
+        let speed = self.wind.speed() * Front::DRIFT;
+        let drift = if speed > 0.0 {
+            (f32::from(Time::HOUR_MINS) / speed).ceil() as u16
+        } else {
+            midnight
+        };
+
+        self.clock() + u64::from(change.min(drift).max(1))
+    }
+
+    fn feel_fatigue(&mut self, from: Tiredness) -> Result<(), Error> {
+        if self.player.tiredness() != from {
+            self.emit(Event::FatigueChanged {
+                from,
+                to: self.player.tiredness(),
+            })?;
+        }
+
+        Ok(())
+    }
+
+    fn feel_chill(&mut self, from: Chill) -> Result<(), Error> {
+        if self.player.chill() != from {
+            self.emit(Event::ChillChanged {
+                from,
+                to: self.player.chill(),
+            })?;
+        }
+
+        Ok(())
+    }
+
+    // whether a wait should end, given the time of day before the last step
+    // and the events it caused
+    fn check(&self, watch: &Watch, was: TimeCat, fired: &[Event]) -> Option<Stop> {
+        if watch.until.is_some_and(|condition| self.holds(condition)) {
+            return Some(Stop::Met);
+        }
+
+        let mut causes = Vec::new();
+        for event in fired {
+            match event {
+                Event::SkyChanged { to: Sky::Rain, .. } => causes.push(Interruption::Storm),
+                Event::PrecipitationChanged { from, to }
+                    if to.thunder && !from.thunder || to.kind == Kind::Hail =>
+                {
+                    causes.push(Interruption::Storm);
+                }
+                // a gale blowing up out of a calm, not one that just gusts
+                Event::WindChanged { to: Wind::High, .. } if watch.wind <= Wind::Light => {
+                    causes.push(Interruption::Wind);
+                }
+                _ => (),
+            }
+        }
+        if was != TimeCat::Night && self.time.classify(&self.daylight()) == TimeCat::Night {
+            causes.push(Interruption::Nightfall);
+        }
+        if self.player.chill() > watch.chill {
+            causes.push(Interruption::Chilled);
+        }
+        let swing = i16::from(self.temp()) - i16::from(watch.temp);
+        if swing >= TEMP_SWING {
+            causes.push(Interruption::Warmer);
+        } else if swing <= -TEMP_SWING {
+            causes.push(Interruption::Colder);
+        }
+
+        causes
+            .into_iter()
+            .find(|cause| !watch.expects(*cause))
+            .map(Stop::Interrupted)
+    }
+
+    // returns whether the sky odds were drawn again on the way, in which case
+    // whatever was due next may no longer be
+    fn advance_clock(&mut self, to: u64) -> Result<bool, Error> {
+        let season = self.season();
+        let mins = to.saturating_sub(self.clock());
+        let (sun, falling) = (self.sun(), self.precipitation());
+        // only the ground near the player is kept up to date (see Stale)
+        for coord in around(self.loc.coord(), GROUND_RANGE) {
+            self.catch_up(coord);
+        }
+        let weather: Vec<(Coord, Sky, i8, Option<Precipitation>)> =
+            around(self.loc.coord(), GROUND_RANGE)
+                .filter(|coord| self.ground.contains_key(coord))
+                .map(|coord| {
+                    (
+                        coord,
+                        self.sky_at(coord),
+                        self.temp_at(coord),
+                        self.precipitation_at(coord),
+                    )
+                })
+                .collect();
+        for (coord, sky, temp, falling) in weather {
+            if let Some((loc, ground, _)) = self.ground.get_mut(&coord) {
+                ground.pass(
+                    mins,
+                    Weathering {
+                        temp,
+                        falling,
+                        sunlight: loc.sunlight(season, sun, sky),
+                        trees: loc.trees(),
+                        season,
+                    },
+                );
+            }
+        }
+        // the air takes a few hours to take on the local humidity
+        let base = self.loc.humidity_base(self.sky());
+        self.humidity += (base - self.humidity) * (1.0 - (-(mins as f32) / HUMIDITY_SETTLE).exp());
+        // the odds of the sky changing follow the pressure, so draw them
+        // again when the barometer would read differently
+        let dial = Dial::classify(self.pressure.hpa());
+        self.pressure.pass(mins);
+        let redrawn = Dial::classify(self.pressure.hpa()) != dial;
+        if redrawn {
+            self.schedule
+                .redraw_sky(&mut self.rng, &self.loc, self.pressure, to);
+        }
+        self.player.pass(
+            mins,
+            Exposure {
+                temp: self.temp(),
+                wind: self.local_wind(),
+                falling,
+            },
+        );
+        let days = self.time.tick(mins);
+        self.date.advance(days);
+
+        if self.season() != season {
+            self.emit(Event::SeasonChanged {
+                from: season,
+                to: self.season(),
+            })?;
+        }
+
+        // the fronts and clouds move on, and bring their weather with them
+        let (sky, falling) = (self.sky(), self.precipitation());
+        for front in &mut self.fronts {
+            front.drift(mins, self.wind, self.wind_dir);
+        }
+        self.field.drift(mins, self.wind, self.wind_dir);
+        self.forget_fronts();
+        self.notice_sky(sky, falling)?;
+
+        Ok(redrawn)
+    }
+
+    // stop keeping the ground up to date where it's out of range, once the
+    // player has moved from `from` to `to`
+    fn leave_ground(&mut self, from: Coord, to: Coord) {
+        let now = self.clock();
+        let left: Vec<(Coord, Stale)> = around(from, GROUND_RANGE)
+            .filter(|coord| !near(*coord, to, GROUND_RANGE) && self.ground.contains_key(coord))
+            .map(|coord| {
+                let stale = Stale {
+                    since: now,
+                    sky: self.sky_at(coord),
+                    temp: self.temp_at(coord),
+                    wind: self.wind,
+                };
+                (coord, stale)
+            })
+            .collect();
+        for (coord, stale) in left {
+            if let Some((_, _, slot @ None)) = self.ground.get_mut(&coord) {
+                *slot = Some(stale);
+            }
+        }
+    }
+
+    // bring the ground at a coord the player has come back near up to date
+    fn catch_up(&mut self, coord: Coord) {
+        let Some((loc, mut ground, Some(stale))) = self.ground.get(&coord).copied() else {
+            return;
+        };
+
+        // it's been through days and nights since, so take the sun as an
+        // average
+        let season = self.season();
+        let falling = Precipitation::classify(stale.sky, stale.temp, stale.wind, season);
+        ground.pass(
+            self.clock().saturating_sub(stale.since),
+            Weathering {
+                temp: stale.temp,
+                falling,
+                sunlight: loc.sunlight(season, self.daylight().mean_strength(), stale.sky),
+                trees: loc.trees(),
+                season,
+            },
+        );
+        self.ground.insert(coord, (loc, ground, None));
+    }
+
+    // a new front forms somewhere upwind
+    fn spawn_front(&mut self) {
+        self.forget_fronts();
+        if self.fronts.len() >= MAX_FRONTS {
+            return;
+        }
+
+        let kind = if self.rng.generate::<bool>() {
+            front::Kind::Storm
+        } else {
+            front::Kind::Clear
+        };
+        // NOTE: nanorand's signed ranges are unreliable, so offset unsigned ones
+        let ahead = f32::from(self.rng.generate_range(30_u8..=60));
+        let aside = f32::from(self.rng.generate_range(0_u8..=60)) - 30.0;
+        let radius = f32::from(self.rng.generate_range(10_u8..=25));
+        let hours = u64::from(self.rng.generate_range(8_u8..=30));
+
+        let coord = self.loc.coord();
+        let (upwind, left) = (
+            self.wind_dir.as_coord_with_magnitude(1),
+            self.wind_dir.left().as_coord_with_magnitude(1),
+        );
+        let n = f32::from(coord.n) + f32::from(upwind.n) * ahead + f32::from(left.n) * aside;
+        let w = f32::from(coord.w) + f32::from(upwind.w) * ahead + f32::from(left.w) * aside;
+        let until = self.clock() + hours * u64::from(Time::HOUR_MINS);
+        // no new front forms over one that's already there
+        let new = Front::new(kind, n, w, radius, until);
+        if !self.fronts.iter().any(|front| front.overlaps(&new)) {
+            self.fronts.push(new);
+        }
+    }
+
+    // drop the fronts that have blown out or drifted out of reach
+    fn forget_fronts(&mut self) {
+        let (now, coord) = (self.clock(), self.loc.coord());
+        self.fronts
+            .retain(|front| front.until() > now && front.distance_to_edge(coord) <= FRONT_RANGE);
+    }
+
+    fn fire(&mut self, process: Process) -> Result<(), Error> {
+        let (sky, falling) = (self.sky(), self.precipitation());
+
+        match process {
+            Process::Temp => {
+                // generate temperature change. each change builds on the
+                // current temperature
                 // HACK: nanorand doesn't do this as expected with signed ints, so have to offset by 1
                 // (THIS IS A WORKAROUND FOR A BUG IN NANORAND)
                 let mut delta: i8 = self.rng.generate_range(
-                    1..=self.loc.temp_max_change(self.season, self.time, self.sky) + 1,
+                    1..=self
+                        .loc
+                        .temp_max_change(self.season(), self.sun(), self.sky())
+                        + 1,
                 );
                 assert!(!delta.is_negative());
 
@@ -134,10 +1027,10 @@ impl<W: Write> Context<W> {
                     self.rng.generate_range(0_u32..=100_000) < self.loc.chance_temp_toward_base();
 
                 // move temp toward or away from base
-                match self
-                    .temp
-                    .cmp(&self.loc.temp_base(self.season, self.time, self.sky))
-                {
+                let base =
+                    self.loc
+                        .temp_base(self.season(), self.sun(), self.sky(), self.elevation());
+                match self.temp().cmp(&base) {
                     Ordering::Less => {
                         if !toward_base {
                             // temp . . . base
@@ -163,147 +1056,339 @@ impl<W: Write> Context<W> {
                     }
                 }
 
-                new_temp = self.temp.saturating_add(delta);
+                // the air holds on to its water as it warms or cools, so
+                // cooling below the dew point saturates it
+                let dew_point = self.dew_point();
+                self.temp = self.temp.saturating_add(delta);
+                self.humidity = relative_humidity(f32::from(self.temp()), dew_point);
             }
 
-            // give wind chance to change
-            if self.rng.generate_range(0_u32..=100_000) < self.loc.chance_wind_change() {
-                if self.rng.generate_range(0_u32..=100_000) < self.loc.chance_wind_increase() {
-                    // increase wind
-                    if self.wind.increase() {
-                        writeln!(self.w, "the wind speeds up.")?;
-                    };
+            Process::Wind => {
+                let from = self.wind;
+                let changed = if self.rng.generate_range(0_u32..=100_000)
+                    < self.loc.chance_wind_increase(self.pressure)
+                {
+                    self.wind.increase()
                 } else {
-                    // decrease wind
-                    if self.wind.decrease() {
-                        writeln!(self.w, "the wind slows down.")?;
+                    self.wind.decrease()
+                };
+                if changed {
+                    self.emit(Event::WindChanged {
+                        from,
+                        to: self.wind,
+                    })?;
+                }
+
+                // the wind swings round as the weather changes, more often
+                // when it picks up, and tends back toward the prevailing
+                let chance = if self.wind > from { 40_000 } else { 15_000 };
+                if self.rng.generate_range(0_u32..=100_000) < chance {
+                    let from = self.wind_dir;
+                    let (left, right) = (from.left(), from.right());
+                    let drift = self.rng.generate_range(0_u32..3) < 2;
+                    self.wind_dir = if drift && left == PREVAILING_WIND {
+                        left
+                    } else if drift && right == PREVAILING_WIND {
+                        right
+                    } else if self.rng.generate::<bool>() {
+                        left
+                    } else {
+                        right
                     };
+                    self.emit(Event::WindShifted {
+                        from,
+                        to: self.wind_dir,
+                    })?;
                 }
             }
 
-            /* give sky chance to change */
-            for (chance, new_sky) in self.loc.chances_sky() {
-                if self.rng.generate_range(0_u32..=100_000) < chance {
-                    match (self.sky, new_sky, self.temp < 0) {
-                        (Sky::Clear, Sky::Clear, _) => (),
-                        (Sky::Clouds, Sky::Clouds, _) => (),
-                        (Sky::Rain, Sky::Rain, _) => (),
-
-                        (Sky::Clear, Sky::Clouds, _) => writeln!(self.w, "it gets cloudy.")?,
-                        (Sky::Clear | Sky::Clouds, Sky::Rain, true) => {
-                            writeln!(self.w, "it starts snowing.")?;
-                        }
-                        (Sky::Clear | Sky::Clouds, Sky::Rain, false) => {
-                            writeln!(self.w, "it starts raining.")?;
-                        }
-                        (Sky::Clouds | Sky::Rain, Sky::Clear, _) => {
-                            writeln!(self.w, "the sky clears up.")?;
-                        }
-                        (Sky::Rain, Sky::Clouds, true) => writeln!(self.w, "it stops snowing.")?,
-                        (Sky::Rain, Sky::Clouds, false) => writeln!(self.w, "it stops raining.")?,
-                    }
+            Process::Sky(new_sky) => self.sky = new_sky,
 
-                    self.sky = new_sky;
-                }
+            Process::Front => self.spawn_front(),
+
+            Process::Pressure => {
+                self.pressure.turn(self.rng.generate());
+                let now = self.clock();
+                self.schedule
+                    .redraw_sky(&mut self.rng, &self.loc, self.pressure, now);
             }
         }
 
-        // notice temperature changes
-        match new_temp.cmp(&self.temp) {
-            Ordering::Less => writeln!(self.w, "it feels colder.")?,
-            Ordering::Greater => writeln!(self.w, "it feels warmer.")?,
-            Ordering::Equal => (),
+        self.notice_sky(sky, falling)
+    }
+
+    // say what's changed overhead, given what it was like before
+    fn notice_sky(&mut self, sky: Sky, falling: Option<Precipitation>) -> Result<(), Error> {
+        if self.sky() != sky {
+            self.emit(Event::SkyChanged {
+                from: sky,
+                to: self.sky(),
+                // what starts or stops falling
+                falling: self.precipitation().or(falling),
+            })?;
+        }
+
+        // the temperature or wind can change what's falling without it
+        // stopping
+        if let (Some(from), Some(to)) = (falling, self.precipitation()) {
+            if from != to {
+                self.emit(Event::PrecipitationChanged { from, to })?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // the weather a little way off
+    fn fmt_horizon(&self, f: &mut Formatter, sky: Sky) -> Result<(), fmt::Error> {
+        // what's coming? only the nearest front of each kind is worth a
+        // mention, and only the ones the wind is bringing closer sound urgent
+        let coord = self.loc.coord();
+        let nearest = |kind| {
+            self.fronts
+                .iter()
+                .filter(|front| front.kind() == kind)
+                .map(|front| (front, front.distance_to_edge(coord)))
+                .filter(|(_, edge)| *edge > 0.0 && *edge <= Front::SIGHT)
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(front, _)| front.bearing(coord))
+        };
+        match nearest(front::Kind::Storm) {
+            Some(bearing) if bearing == self.wind_dir && self.wind > Wind::None => {
+                writeln!(f, "dark clouds are gathering to the {}.", bearing)?;
+            }
+            Some(bearing) => writeln!(f, "dark clouds hang on the horizon to the {}.", bearing)?,
+            None => (),
+        }
+        if sky != Sky::Clear {
+            if let Some(bearing) = nearest(front::Kind::Clear) {
+                writeln!(f, "the sky is brightening to the {}.", bearing)?;
+            }
+        }
+
+        // showers can be seen falling, or not, a little way off
+        let mut nearby = [
+            Direction::North,
+            Direction::East,
+            Direction::South,
+            Direction::West,
+        ]
+        .into_iter()
+        .map(|direction| {
+            let mut there = coord;
+            there += direction.as_coord_with_magnitude(LOOK_AROUND);
+            (direction, self.precipitation_at(there))
+        });
+        if self.precipitation().is_none() {
+            if let Some((direction, Some(there))) = nearby.find(|(_, there)| there.is_some()) {
+                writeln!(f, "{} is falling to the {}.", there.kind, direction)?;
+            }
+        } else if let Some((direction, _)) = nearby.find(|(_, there)| there.is_none()) {
+            writeln!(f, "it looks drier to the {}.", direction)?;
         }
-        self.temp = new_temp;
 
         Ok(())
     }
 
+    // the description without anything that depends on which way the player
+    // is facing, so that turning around doesn't count as a change
+    fn surroundings(&self) -> String {
+        Surroundings(self).to_string()
+    }
+
     fn description_changed(&self) -> bool {
-        self.to_string() != self.last_desc
+        self.surroundings() != self.last_desc
     }
-}
 
-impl<W: Write> Display for Context<W> {
-    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+    // the description, as seen facing `heading`
+    fn fmt_facing(&self, f: &mut Formatter, heading: Option<Direction>) -> Result<(), fmt::Error> {
         // where are we?
         match self.loc {
             Location::Forest(_) => writeln!(f, "you are in a forest.")?,
+            Location::Meadow(_) => writeln!(f, "you are in an open meadow.")?,
+            Location::LakeShore(_) => writeln!(f, "you are on the shore of a lake.")?,
+            Location::Ridge(_) => writeln!(f, "you are up on a mountain ridge.")?,
+            Location::Bog(_) => writeln!(f, "you are in a bog.")?,
+            Location::RiverValley(_) => writeln!(f, "you are in a river valley.")?,
         };
+        if let Some(feature) = self.world.feature(self.loc.coord()) {
+            writeln!(f, "{}", feature)?;
+        }
+
+        // what's the ground like? are we climbing?
+        let gradient = self.world.terrain().gradient(self.loc.coord());
+        match Slope::classify(gradient.steepness()) {
+            Slope::Flat => writeln!(f, "the ground is flat here.")?,
+            slope => {
+                writeln!(f, "you are standing on {} terrain.", slope)?;
+                if let Some(heading) = heading {
+                    let rise = gradient.rise_toward(heading);
+                    if rise > Terrain::NOTICEABLE_RISE {
+                        writeln!(f, "the ground rises ahead of you.")?;
+                    } else if rise < -Terrain::NOTICEABLE_RISE {
+                        writeln!(f, "the ground falls away ahead of you.")?;
+                    } else {
+                        writeln!(f, "the slope runs across your path.")?;
+                    }
+                }
+            }
+        }
+
+        // what's underfoot?
+        let ground = self.ground();
+        write!(f, "{}", ground)?;
 
         // is it cloudy?
-        if self.sky == Sky::Clouds {
+        let sky = self.sky();
+        if sky == Sky::Clouds {
             writeln!(f, "it is cloudy.")?;
         }
 
         // what's the air like?
-        match (self.wind, self.sky, TempCat::classify(self.temp)) {
-            (
-                Wind::None,
-                Sky::Clear | Sky::Clouds,
-                temp @ (TempCat::Freezing | TempCat::Chilly),
-            ) => {
-                writeln!(f, "it is {}.", temp)?;
-            }
-            (Wind::None, Sky::Clear | Sky::Clouds, TempCat::Neutral) => {
-                writeln!(f, "the air is still.")?;
-            }
-            (Wind::None, Sky::Clear | Sky::Clouds, temp @ (TempCat::Warm | TempCat::Hot)) => {
-                writeln!(f, "the air is {} and still.", temp)?;
-            }
-            (Wind::None, Sky::Rain, TempCat::Freezing) => writeln!(f, "it is snowing.")?,
-            (Wind::None, Sky::Rain, TempCat::Hot) => writeln!(f, "it is hot and rainy.")?,
-            (
-                Wind::None | Wind::Light,
-                Sky::Rain,
-                TempCat::Chilly | TempCat::Neutral | TempCat::Warm,
-            ) => writeln!(f, "it is raining.")?,
-            (Wind::Light, Sky::Rain, TempCat::Freezing) => {
-                writeln!(f, "it is snowing with a frigid breeze.")?;
-            }
-            (Wind::Light, Sky::Rain, TempCat::Hot) => {
-                writeln!(f, "it is raining with a hot breeze.")?;
-            }
-            (Wind::Light, Sky::Clear | Sky::Clouds, temp) => {
-                writeln!(f, "there is a {} breeze.", temp)?;
-            }
-            (Wind::Medium, Sky::Clear | Sky::Clouds, TempCat::Freezing) => {
-                writeln!(f, "there is a bitter wind.")?;
-            }
-            (Wind::Medium, Sky::Clear | Sky::Clouds, temp) => {
-                writeln!(f, "there is a {} wind.", temp)?;
-            }
-            (Wind::Medium, Sky::Rain, TempCat::Freezing) => {
-                writeln!(f, "it is snowing with a bitter wind.")?;
-            }
-            (Wind::Medium, Sky::Rain, TempCat::Chilly | TempCat::Neutral | TempCat::Warm) => {
-                writeln!(f, "it is raining and windy.")?;
-            }
-            (Wind::Medium, Sky::Rain, TempCat::Hot) => {
-                writeln!(f, "there are hot gusts of rain.")?;
-            }
-            (Wind::High, Sky::Clear | Sky::Clouds, TempCat::Freezing) => {
-                writeln!(f, "the wind howls and bites.")?;
-            }
-            (Wind::High, Sky::Clear | Sky::Clouds, TempCat::Chilly | TempCat::Neutral) => {
-                writeln!(f, "the wind howls.")?
-            }
-            (Wind::High, Sky::Clear | Sky::Clouds, temp @ (TempCat::Warm | TempCat::Hot)) => {
-                writeln!(f, "there are strong gusts of {} wind.", temp)?
+        let wind = self.local_wind();
+        let temp = TempCat::classify(self.apparent_temp());
+        match self.precipitation() {
+            None => match (wind, temp) {
+                (Wind::None, temp @ (TempCat::Freezing | TempCat::Chilly)) => {
+                    writeln!(f, "it is {}.", temp)?;
+                }
+                (Wind::None, TempCat::Neutral) => writeln!(f, "the air is still.")?,
+                (Wind::None, temp @ (TempCat::Warm | TempCat::Hot)) => {
+                    writeln!(f, "the air is {} and still.", temp)?;
+                }
+                (Wind::Light, temp) => writeln!(f, "there is a {} breeze.", temp)?,
+                (Wind::Medium, TempCat::Freezing) => writeln!(f, "there is a bitter wind.")?,
+                (Wind::Medium, temp) => writeln!(f, "there is a {} wind.", temp)?,
+                (Wind::High, TempCat::Freezing) => writeln!(f, "the wind howls and bites.")?,
+                (Wind::High, TempCat::Chilly | TempCat::Neutral) => {
+                    writeln!(f, "the wind howls.")?;
+                }
+                (Wind::High, temp @ (TempCat::Warm | TempCat::Hot)) => {
+                    writeln!(f, "there are strong gusts of {} wind.", temp)?;
+                }
+            },
+
+            Some(falling) => {
+                match (falling.kind, wind, temp) {
+                    (Kind::Rain, Wind::None, TempCat::Hot) => writeln!(f, "it is hot and rainy.")?,
+                    (Kind::Rain, Wind::Light, TempCat::Hot) => {
+                        writeln!(f, "it is raining with a hot breeze.")?;
+                    }
+                    (Kind::Rain, Wind::None | Wind::Light, _) => writeln!(f, "it is raining.")?,
+                    (Kind::Rain, Wind::Medium, TempCat::Hot) => {
+                        writeln!(f, "there are hot gusts of rain.")?;
+                    }
+                    (Kind::Rain, Wind::Medium, _) => writeln!(f, "it is raining and windy.")?,
+                    (Kind::Rain, Wind::High, TempCat::Hot) => {
+                        writeln!(f, "the hot rain blows furiously.")?;
+                    }
+                    (Kind::Rain, Wind::High, _) => writeln!(f, "it is raining furiously.")?,
+
+                    (Kind::Snow, Wind::None, _) => writeln!(f, "it is snowing.")?,
+                    (Kind::Snow, Wind::Light, _) => {
+                        writeln!(f, "it is snowing with a frigid breeze.")?;
+                    }
+                    (Kind::Snow, Wind::Medium, _) => {
+                        writeln!(f, "it is snowing with a bitter wind.")?;
+                    }
+                    (Kind::Snow, Wind::High, _) => {
+                        writeln!(f, "the wind howls and bites. it is snowing furiously.")?;
+                    }
+
+                    (Kind::Sleet, Wind::None | Wind::Light, _) => {
+                        writeln!(f, "wet sleet is falling.")?;
+                    }
+                    (Kind::Sleet, Wind::Medium, _) => {
+                        writeln!(f, "sleet drives in on the wind.")?;
+                    }
+                    (Kind::Sleet, Wind::High, _) => {
+                        writeln!(f, "the wind flings sleet into your face.")?;
+                    }
+
+                    (Kind::FreezingRain, _, _) if falling.intensity == Intensity::Heavy => {
+                        writeln!(f, "freezing rain lashes down, coating everything in ice.")?;
+                    }
+                    (Kind::FreezingRain, _, _) => {
+                        writeln!(
+                            f,
+                            "a freezing rain is falling, glazing everything with ice."
+                        )?;
+                    }
+
+                    (Kind::Hail, _, _) => writeln!(f, "hail hammers down around you.")?,
+                }
+
+                if falling.thunder {
+                    writeln!(f, "lightning flickers and thunder rolls overhead.")?;
+                }
             }
-            (Wind::High, Sky::Rain, TempCat::Freezing) => {
-                writeln!(f, "the wind howls and bites. it is snowing furiously.")?;
+        }
+
+        // which way is it blowing?
+        if wind > Wind::None {
+            match heading {
+                Some(heading) if heading == self.wind_dir.opposite() => {
+                    writeln!(f, "the wind is at your back.")?;
+                }
+                Some(heading) if heading == self.wind_dir => {
+                    writeln!(f, "the wind blows in your face.")?;
+                }
+                Some(heading) if heading.left() == self.wind_dir => {
+                    writeln!(f, "the wind blows from your left.")?;
+                }
+                Some(_) => writeln!(f, "the wind blows from your right.")?,
+                None => writeln!(f, "the wind blows from the {}.", self.wind_dir)?,
             }
-            (Wind::High, Sky::Rain, TempCat::Chilly | TempCat::Neutral | TempCat::Warm) => {
-                writeln!(f, "it is raining furiously.")?;
+        }
+        match self.shelter_at(self.loc.coord()) {
+            Shelter::Lee => writeln!(f, "the slope shelters you from the wind.")?,
+            Shelter::Open => (),
+            Shelter::Windward => writeln!(f, "the slope lies open to the wind.")?,
+        }
+
+        // nothing further off can be made out in the dark
+        if self.time.classify(&self.daylight()) != TimeCat::Night {
+            self.fmt_horizon(f, sky)?;
+        }
+
+        // is anything hanging in the air or settling on the ground? it all
+        // comes down to how close the air is to its dew point.
+        let time_cat = self.time.classify(&self.daylight());
+        let mut spread = f32::from(self.temp()) - self.dew_point();
+        if matches!(
+            self.loc,
+            Location::LakeShore(_) | Location::Bog(_) | Location::RiverValley(_)
+        ) {
+            spread -= 1.0; // open water keeps the air near the ground damp
+        }
+        if self.precipitation().is_none() && ground.snow() < 1.0 {
+            match time_cat {
+                TimeCat::Dawn if wind == Wind::None && spread < 1.0 => {
+                    writeln!(f, "a thick fog hangs in the air.")?;
+                }
+                TimeCat::Dawn if wind <= Wind::Light && spread < 2.5 => {
+                    writeln!(f, "wisps of mist drift over the ground.")?;
+                }
+                _ => (),
             }
-            (Wind::High, Sky::Rain, TempCat::Hot) => {
-                writeln!(f, "the hot rain blows furiously.")?;
+
+            match (time_cat, sky) {
+                (TimeCat::Night | TimeCat::Dawn, Sky::Clear)
+                    if self.temp() <= 0 && spread < 5.0 =>
+                {
+                    writeln!(f, "frost glitters on the ground.")?;
+                }
+                (TimeCat::Dawn | TimeCat::Morning, _)
+                    if self.season() == Season::Summer && self.temp() > 0 && spread < 4.0 =>
+                {
+                    writeln!(f, "the grass is wet with dew.")?;
+                }
+                _ => (),
             }
         }
 
         // what's the time of day? we might have very little to go off of.
-        match (self.time.classify(self.season), self.sky) {
+        match (self.time.classify(&self.daylight()), sky) {
             (TimeCat::Dawn, Sky::Clear) => writeln!(f, "the sun is rising.")?,
             (TimeCat::Dusk, Sky::Clear) => writeln!(f, "the sun is setting.")?,
             (TimeCat::Dawn | TimeCat::Dusk, _) => writeln!(f, "the sky is dark grey.")?,
@@ -322,22 +1407,96 @@ impl<W: Write> Display for Context<W> {
     }
 }
 
+impl<O: Observer> Display for Context<O> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        self.fmt_facing(f, self.heading)
+    }
+}
+
+// the description as it would be given facing no way in particular
+struct Surroundings<'a, O: Observer>(&'a Context<O>);
+
+impl<O: Observer> Display for Surroundings<'_, O> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        self.0.fmt_facing(f, None)
+    }
+}
+
+// what to look out for while waiting
+struct Watch {
+    until: Option<Condition>,
+    sleeping: bool,
+    // when the wait began
+    wind: Wind,
+    temp: i8,
+    chill: Chill,
+}
+
+impl Watch {
+    // whether this is what's being waited for anyway, or doesn't matter
+    fn expects(&self, cause: Interruption) -> bool {
+        if self.sleeping {
+            // a sleeper doesn't notice the dark or the warmth
+            return matches!(cause, Interruption::Nightfall | Interruption::Warmer);
+        }
+        // waiting for something can take all night, so only a plain wait is
+        // cut short by the dark
+        matches!(
+            (cause, self.until),
+            (Interruption::Nightfall, Some(_)) | (Interruption::Wind, Some(Condition::Calm))
+        )
+    }
+}
+
+// why a wait ended early
+enum Stop {
+    Met,
+    Interrupted(Interruption),
+}
+
+// how the player picks where to go
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Navigation {
+    Compass,
+    Relative, // up, down and across the local slope
+}
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Location {
     Forest(Coord),
+    Meadow(Coord),
+    LakeShore(Coord),
+    Ridge(Coord),
+    Bog(Coord),
+    RiverValley(Coord),
 }
 
 impl Location {
-    fn sunlight(&self, season: Season, time: Time, sky: Sky) -> f32 {
-        let sky_sun = season.sunlight_level(time);
-
-        let sun_intensity = match season {
-            Season::Spring => 0.80,
-            Season::Summer => 1.00,
-            Season::Autumn => 0.90,
-            Season::Winter => 0.70,
-        };
+    pub const fn coord(&self) -> Coord {
+        match self {
+            Self::Forest(coord)
+            | Self::Meadow(coord)
+            | Self::LakeShore(coord)
+            | Self::Ridge(coord)
+            | Self::Bog(coord)
+            | Self::RiverValley(coord) => *coord,
+        }
+    }
+
+    // how much of the ground is under trees
+    pub const fn trees(&self) -> f32 {
+        match self {
+            Self::Forest(_) => 1.0,
+            Self::RiverValley(_) => 0.4,
+            Self::Bog(_) | Self::LakeShore(_) => 0.2,
+            Self::Meadow(_) => 0.1,
+            Self::Ridge(_) => 0.0,
+        }
+    }
 
+    // sun is the strength of the sun above the canopy, as given by Daylight::strength
+    pub fn sunlight(&self, season: Season, sun: f32, sky: Sky) -> f32 {
+        // how much the canopy lets through
         let sky_visibility = (match self {
             Self::Forest(_) => match season {
                 Season::Spring => 0.8,
@@ -345,19 +1504,28 @@ impl Location {
                 Season::Autumn => 0.7,
                 Season::Winter => 0.9,
             },
+            Self::RiverValley(_) => match season {
+                Season::Spring => 0.85,
+                Season::Summer => 0.75,
+                Season::Autumn => 0.8,
+                Season::Winter => 0.95,
+            },
+            Self::Bog(_) => 0.95,
+            Self::Meadow(_) | Self::LakeShore(_) | Self::Ridge(_) => 1.0,
         }) * (match sky {
             Sky::Clear => 1.0,
             Sky::Clouds => 0.7,
             Sky::Rain => 0.6,
         });
 
-        sky_sun * sun_intensity * sky_visibility
+        sun * sky_visibility
     }
 }
 
 #[allow(clippy::zero_prefixed_literal)]
 impl Location {
-    pub fn temp_base(&self, season: Season, time: Time, sky: Sky) -> i8 {
+    // the temperature the air here tends toward, `elevation` metres up
+    pub fn temp_base(&self, season: Season, sun: f32, sky: Sky, elevation: f32) -> i8 {
         let base = match self {
             Self::Forest(_) => match season {
                 Season::Spring => 0,
@@ -365,55 +1533,151 @@ impl Location {
                 Season::Autumn => 9,
                 Season::Winter => -5,
             },
+            Self::Meadow(_) => match season {
+                Season::Spring => 1,
+                Season::Summer => 8,
+                Season::Autumn => 9,
+                Season::Winter => -6,
+            },
+            // the water evens things out
+            Self::LakeShore(_) => match season {
+                Season::Spring => 0,
+                Season::Summer => 5,
+                Season::Autumn => 10,
+                Season::Winter => -3,
+            },
+            Self::Ridge(_) => match season {
+                Season::Spring => -4,
+                Season::Summer => 2,
+                Season::Autumn => 4,
+                Season::Winter => -10,
+            },
+            Self::Bog(_) => match season {
+                Season::Spring => 0,
+                Season::Summer => 7,
+                Season::Autumn => 8,
+                Season::Winter => -6,
+            },
+            // cold air pools in the valley at night
+            Self::RiverValley(_) => match season {
+                Season::Spring => -1,
+                Season::Summer => 6,
+                Season::Autumn => 8,
+                Season::Winter => -7,
+            },
         };
 
         const DIURNAL_VAR: f32 = 10.0;
-        let sun_bias = (self.sunlight(season, time, sky) - 0.5) * DIURNAL_VAR * 2.0;
-        base + sun_bias as i8
+        let sun_bias = (self.sunlight(season, sun, sky) - 0.5) * DIURNAL_VAR * 2.0;
+        base + (sun_bias - Terrain::lapse(elevation)) as i8
     }
 
-    pub fn temp_max_change(&self, season: Season, time: Time, sky: Sky) -> i8 {
+    pub fn temp_max_change(&self, season: Season, sun: f32, sky: Sky) -> i8 {
         const MAX_CHANGE: f32 = 4.0;
         // NOTE: should be positive, otherwise toward base chance is flipped
-        (self.sunlight(season, time, sky) * MAX_CHANGE) as i8 + 1
+        (self.sunlight(season, sun, sky) * MAX_CHANGE) as i8 + 1
     }
 
     pub const fn chance_temp_toward_base(&self) -> u32 {
         match self {
-            Self::Forest(_) => 60_000,
+            Self::Forest(_) | Self::Meadow(_) | Self::Bog(_) | Self::RiverValley(_) => 60_000,
+            Self::LakeShore(_) => 70_000,
+            Self::Ridge(_) => 55_000,
         }
     }
 
     pub const fn chance_temp_change(&self) -> u32 {
         match self {
-            Self::Forest(_) => 16_667, // 1 change / 10 mins
+            Self::Forest(_) | Self::Bog(_) | Self::RiverValley(_) => 16_667, // 1 change / 10 mins
+            Self::Meadow(_) | Self::Ridge(_) => 20_000,                      // 1 change / 8 mins
+            Self::LakeShore(_) => 12_500,                                    // 1 change / 13 mins
         }
     }
 
     pub const fn chance_wind_change(&self) -> u32 {
         match self {
-            Self::Forest(_) => 1_667, // 1 change / 1 hr
+            Self::Forest(_) | Self::Bog(_) => 1_667, // 1 change / 1 hr
+            Self::Meadow(_) | Self::LakeShore(_) => 2_500, // 1 change / 40 mins
+            Self::Ridge(_) => 3_333,                 // 1 change / 30 mins
+            Self::RiverValley(_) => 1_389,           // 1 change / 1.2 hrs
         }
     }
 
-    pub const fn chance_wind_increase(&self) -> u32 {
-        match self {
+    // low or falling pressure whips the wind up
+    pub fn chance_wind_increase(&self, pressure: Pressure) -> u32 {
+        const UNSETTLED: f32 = 10_000.0;
+        let chance: u32 = match self {
             Self::Forest(_) => 50_000,
-        }
+            Self::Meadow(_) | Self::LakeShore(_) => 55_000,
+            Self::Ridge(_) => 65_000,
+            Self::Bog(_) => 45_000,
+            Self::RiverValley(_) => 40_000,
+        };
+        (chance as f32 + pressure.unsettled() * UNSETTLED).clamp(10_000.0, 90_000.0) as u32
     }
 
-    pub const fn chances_sky(&self) -> [(u32, Sky); 3] {
-        match self {
-            Self::Forest(_) => [
+    // these are the odds at steady, average pressure
+    pub fn chances_sky(&self, pressure: Pressure) -> [(u32, Sky); 3] {
+        let chances = match self {
+            Self::Forest(_) | Self::Meadow(_) | Self::RiverValley(_) => [
                 (0_208, Sky::Clear),  // 1 change / 8 hr
                 (0_417, Sky::Clouds), // 1 change / 4 hrs
                 (0_139, Sky::Rain),   // 1 change / 12 hrs
             ],
+            Self::LakeShore(_) => [
+                (0_208, Sky::Clear),  // 1 change / 8 hrs
+                (0_500, Sky::Clouds), // 1 change / 3.3 hrs
+                (0_167, Sky::Rain),   // 1 change / 10 hrs
+            ],
+            Self::Ridge(_) => [
+                (0_250, Sky::Clear),  // 1 change / 6.7 hrs
+                (0_500, Sky::Clouds), // 1 change / 3.3 hrs
+                (0_167, Sky::Rain),   // 1 change / 10 hrs
+            ],
+            Self::Bog(_) => [
+                (0_174, Sky::Clear),  // 1 change / 9.6 hrs
+                (0_417, Sky::Clouds), // 1 change / 4 hrs
+                (0_167, Sky::Rain),   // 1 change / 10 hrs
+            ],
+        };
+        chances.map(|(chance, sky)| ((chance as f32 * pressure.sky_factor(sky)) as u32, sky))
+    }
+
+    // the relative humidity the air here settles toward
+    pub fn humidity_base(&self, sky: Sky) -> f32 {
+        let base = match self {
+            Self::LakeShore(_) | Self::Bog(_) => 0.85,
+            Self::RiverValley(_) => 0.8,
+            Self::Forest(_) => 0.75,
+            Self::Meadow(_) => 0.65,
+            Self::Ridge(_) => 0.55,
+        };
+        match sky {
+            Sky::Clear => base,
+            Sky::Clouds => base + 0.1,
+            Sky::Rain => 1.0,
         }
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+// the coords up to `range` away from `coord`, in a square
+fn around(coord: Coord, range: i8) -> impl Iterator<Item = Coord> {
+    (-range..=range).flat_map(move |n| {
+        (-range..=range).filter_map(move |w| {
+            Some(Coord {
+                n: coord.n.checked_add(n)?,
+                w: coord.w.checked_add(w)?,
+            })
+        })
+    })
+}
+
+fn near(a: Coord, b: Coord, range: i8) -> bool {
+    (i16::from(a.n) - i16::from(b.n)).abs() <= i16::from(range)
+        && (i16::from(a.w) - i16::from(b.w)).abs() <= i16::from(range)
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 pub struct Coord {
     pub n: i8, // north
     pub w: i8, // west
@@ -432,7 +1696,7 @@ impl AddAssign for Coord {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Time {
     // NOTE: never exceeds Self::DAY_MINS
     mins: u16,
@@ -449,26 +1713,34 @@ impl Time {
         }
     }
 
+    pub const fn from_mins(mins: u16) -> Self {
+        Self {
+            mins: mins % Self::DAY_MINS,
+        }
+    }
+
+    // minutes since midnight
+    pub const fn mins(&self) -> u16 {
+        self.mins
+    }
+
     pub fn get(&self) -> (u8, u8) {
         let hour = self.mins / Self::HOUR_MINS;
         let min = self.mins % Self::HOUR_MINS;
         (hour as u8, min as u8)
     }
 
-    pub fn tick(&mut self, hours: u8, mins: u8) {
-        for _ in 0..hours {
-            self.mins += Self::HOUR_MINS;
-            self.wrap_mins();
-        }
-
-        self.mins += u16::from(mins);
-        self.wrap_mins();
+    // returns how many times midnight was passed
+    pub fn tick(&mut self, mins: u64) -> u64 {
+        let total = u64::from(self.mins) + mins;
+        self.mins = (total % u64::from(Self::DAY_MINS)) as u16;
+        total / u64::from(Self::DAY_MINS)
     }
 
-    pub fn classify(&self, season: Season) -> TimeCat {
-        let (sunrise, sunset) = season.sunlight_times();
+    pub fn classify(&self, daylight: &Daylight) -> TimeCat {
+        let (sunrise, sunset) = (daylight.sunrise(), daylight.sunset());
 
-        if self.mins < sunrise.mins || self.mins > sunset.mins {
+        if !daylight.is_up(*self) {
             TimeCat::Night
         } else {
             // stretch the sunset/sunrise times so at 0 is dawn, and 255 is dusk.
@@ -484,11 +1756,6 @@ impl Time {
             }
         }
     }
-
-    // uphold self.mins invariant
-    fn wrap_mins(&mut self) {
-        self.mins %= Self::DAY_MINS;
-    }
 }
 
 impl Display for Time {
@@ -498,6 +1765,66 @@ impl Display for Time {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Date {
+    year: u16,
+    // NOTE: never exceeds Self::YEAR_DAYS, and day 0 is the first of january
+    day: u16,
+}
+
+impl Date {
+    pub const YEAR_DAYS: u16 = 365;
+
+    pub const fn new(year: u16, day: u16) -> Self {
+        Self {
+            year: year + day / Self::YEAR_DAYS,
+            day: day % Self::YEAR_DAYS,
+        }
+    }
+
+    pub const fn get(&self) -> (u16, u16) {
+        (self.year, self.day)
+    }
+
+    pub fn advance(&mut self, days: u64) {
+        let day = u64::from(self.day) + days;
+        let years = day / u64::from(Self::YEAR_DAYS);
+        self.year = self
+            .year
+            .saturating_add(u16::try_from(years).unwrap_or(u16::MAX));
+        self.day = (day % u64::from(Self::YEAR_DAYS)) as u16;
+    }
+
+    // minutes from the start of year 1 until this date at the given time
+    pub fn clock(&self, time: Time) -> u64 {
+        let days = u64::from(self.year.saturating_sub(1)) * u64::from(Self::YEAR_DAYS)
+            + u64::from(self.day);
+        days * u64::from(Time::DAY_MINS) + u64::from(time.mins())
+    }
+
+    // the seasons are the other way round south of the equator
+    pub fn season(&self, latitude: f32) -> Season {
+        let season = match self.day {
+            0..=58 => Season::Winter,
+            59..=150 => Season::Spring,
+            151..=242 => Season::Summer,
+            243..=333 => Season::Autumn,
+            _ => Season::Winter,
+        };
+        if latitude < 0.0 {
+            season.opposite()
+        } else {
+            season
+        }
+    }
+}
+
+impl Display for Date {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        write!(f, "year {} day {}", self.year, self.day + 1)
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum TimeCat {
     Dawn,
@@ -517,24 +1844,12 @@ pub enum Season {
 }
 
 impl Season {
-    pub fn sunlight_times(&self) -> (Time, Time) {
+    pub const fn opposite(&self) -> Self {
         match self {
-            Season::Spring => (Time::new(8, 0), Time::new(17, 0)),
-            Season::Summer => (Time::new(4, 0), Time::new(22, 30)),
-            Season::Autumn => (Time::new(5, 0), Time::new(21, 30)),
-            Season::Winter => (Time::new(9, 0), Time::new(15, 0)),
-        }
-    }
-
-    pub fn sunlight_level(&self, time: Time) -> f32 {
-        let (sunrise, sunset) = self.sunlight_times();
-
-        if time.mins < sunrise.mins || time.mins > sunset.mins {
-            0.0
-        } else {
-            ((f32::from(time.mins - sunrise.mins) * consts::PI)
-                / f32::from(sunset.mins - sunrise.mins))
-            .sin()
+            Self::Spring => Self::Autumn,
+            Self::Summer => Self::Winter,
+            Self::Autumn => Self::Spring,
+            Self::Winter => Self::Summer,
         }
     }
 }
@@ -546,7 +1861,7 @@ pub enum Sky {
     Rain,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
 pub enum Wind {
     None,
     Light,
@@ -580,6 +1895,60 @@ impl Wind {
         *self = new;
         old != new
     }
+
+    // rough speed in km/h
+    pub const fn speed(&self) -> f32 {
+        match self {
+            Self::None => 0.0,
+            Self::Light => 10.0,
+            Self::Medium => 25.0,
+            Self::High => 50.0,
+        }
+    }
+}
+
+// how cold the air feels on the skin. below 10C this is the usual wind chill
+// index, fading out to a slight cooling by 20C. rain makes it feel colder
+// still.
+pub fn apparent_temp(temp: f32, wind: Wind, wet: bool) -> f32 {
+    let speed = wind.speed();
+    let breezy = temp - 0.04 * speed;
+    let chill = if speed > 4.8 {
+        let v = speed.powf(0.16);
+        13.12 + 0.6215 * temp - 11.37 * v + 0.3965 * temp * v
+    } else {
+        temp
+    };
+
+    let felt = if temp <= 10.0 {
+        chill.min(temp)
+    } else if temp < 20.0 {
+        let t = (temp - 10.0) / 10.0;
+        chill.min(temp) * (1.0 - t) + breezy * t
+    } else {
+        breezy
+    };
+    if wet {
+        felt - 2.0
+    } else {
+        felt
+    }
+}
+
+// magnus formula constants
+const MAGNUS_B: f32 = 17.62;
+const MAGNUS_C: f32 = 243.12; // celcius
+
+// the temperature the air would have to cool to before water condenses out
+pub fn dew_point(temp: f32, humidity: f32) -> f32 {
+    let gamma = humidity.max(0.01).ln() + MAGNUS_B * temp / (MAGNUS_C + temp);
+    MAGNUS_C * gamma / (MAGNUS_B - gamma)
+}
+
+// the relative humidity of air at `temp` that has this dew point
+pub fn relative_humidity(temp: f32, dew_point: f32) -> f32 {
+    let gamma = |t: f32| MAGNUS_B * t / (MAGNUS_C + t);
+    (gamma(dew_point) - gamma(temp)).exp().min(1.0)
 }
 
 #[derive(Clone, Copy, PartialEq)]
@@ -615,3 +1984,334 @@ impl Display for TempCat {
         write!(f, "{}", adjective)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::event::English;
+    use crate::pressure::Tendency;
+    use crate::save::Persist;
+    use crate::sun;
+
+    use std::io::{self, Sink};
+
+    fn walk(ctx: &mut Context<English<Sink>>) {
+        for action in [
+            Action::Go(Direction::North),
+            Action::Wait(Span::from_hours(5)),
+            Action::Go(Direction::West),
+            Action::Wait(Span::from_hours(30)),
+        ] {
+            ctx.act(action).unwrap();
+        }
+    }
+
+    #[test]
+    fn same_seed_same_world() {
+        let (mut a, mut b) = (
+            Context::with_seed(English::new(io::sink()), 11, sun::DEFAULT_LATITUDE),
+            Context::with_seed(English::new(io::sink()), 11, sun::DEFAULT_LATITUDE),
+        );
+        walk(&mut a);
+        walk(&mut b);
+        assert_eq!(a.to_string(), b.to_string());
+        assert_eq!(a.events(), b.events());
+    }
+
+    #[test]
+    fn loading_continues_the_same_stream() {
+        let mut played = Context::with_seed(English::new(io::sink()), 3, sun::DEFAULT_LATITUDE);
+        walk(&mut played);
+        let mut save = Vec::new();
+        played.save(&mut save).unwrap();
+
+        let mut loaded = Context::with_seed(English::new(io::sink()), 99, sun::DEFAULT_LATITUDE);
+        loaded.load(save.as_slice()).unwrap();
+        assert_eq!(loaded.to_string(), played.to_string());
+
+        walk(&mut played);
+        walk(&mut loaded);
+        assert_eq!(loaded.events(), played.events());
+        assert_eq!(loaded.to_string(), played.to_string());
+        assert_eq!(loaded.rng.blocks(), played.rng.blocks());
+    }
+
+    // hears everything, to check what a front-end would be told
+    #[derive(Debug, Default)]
+    struct Recorder(Vec<Event>);
+
+    impl Observer for Recorder {
+        fn observe(&mut self, event: &Event) -> Result<(), Error> {
+            self.0.push(event.clone());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn observer_hears_the_events() {
+        let mut ctx = Context::with_seed(Recorder::default(), 5, sun::DEFAULT_LATITUDE);
+        ctx.act(Action::Wait(Span::from_hours(2))).unwrap();
+        assert_eq!(ctx.observer.0, ctx.events());
+        assert!(matches!(ctx.events()[0], Event::Waited { .. }));
+        assert!(ctx
+            .events()
+            .iter()
+            .any(|event| matches!(event, Event::TimePassed { .. })));
+    }
+
+    #[test]
+    fn turning_around_changes_nothing() {
+        let mut ctx = Context::with_seed(English::new(io::sink()), 5, sun::DEFAULT_LATITUDE);
+        ctx.act(Action::Describe).unwrap();
+        for heading in [Direction::North, Direction::East, Direction::South] {
+            ctx.heading = Some(heading);
+            assert!(!ctx.description_changed());
+        }
+    }
+
+    #[test]
+    fn edge_of_the_world_blocks() {
+        let mut ctx = Context::with_seed(English::new(io::sink()), 5, sun::DEFAULT_LATITUDE);
+        ctx.loc = ctx.world.location(Coord { n: i8::MAX, w: 0 });
+        ctx.act(Action::Describe).unwrap();
+        let now = ctx.clock();
+        ctx.act(Action::Go(Direction::North)).unwrap();
+        assert_eq!(
+            ctx.events(),
+            [Event::Blocked {
+                direction: Direction::North
+            }]
+        );
+        assert_eq!(ctx.clock(), now);
+        assert!(!ctx.available_directions().contains(&Direction::North));
+    }
+
+    #[test]
+    fn navigation_is_not_saved() {
+        let mut relative = Context::with_seed(English::new(io::sink()), 5, sun::DEFAULT_LATITUDE);
+        relative.set_navigation(Navigation::Relative);
+        let mut save = Vec::new();
+        relative.save(&mut save).unwrap();
+
+        let mut ctx = Context::with_seed(English::new(io::sink()), 5, sun::DEFAULT_LATITUDE);
+        ctx.load(save.as_slice()).unwrap();
+        assert_eq!(ctx.navigation(), Navigation::Compass);
+    }
+
+    #[test]
+    fn seasons_flip_south_of_the_equator() {
+        let midwinter = Date::new(1, 0);
+        assert_eq!(midwinter.season(60.0), Season::Winter);
+        assert_eq!(midwinter.season(-35.0), Season::Summer);
+        assert_eq!(Date::new(1, 100).season(-35.0), Season::Autumn);
+
+        let ctx = Context::with_seed(English::new(io::sink()), 5, -35.0);
+        assert_eq!(ctx.season(), Season::Summer);
+        // and the long days come with it
+        let daylight = ctx.daylight();
+        assert!(daylight.sunset().mins() - daylight.sunrise().mins() > 12 * 60);
+    }
+
+    #[test]
+    fn southern_spawns_start_in_summer() {
+        for seed in 0..20 {
+            let north = Context::with_seed(English::new(io::sink()), seed, 35.0);
+            let south = Context::with_seed(English::new(io::sink()), seed, -35.0);
+            assert_eq!(south.ground().snow(), 0.0, "seed {seed}");
+            assert!(south.temp() > north.temp(), "seed {seed}");
+        }
+    }
+
+    fn watch(until: Option<Condition>, sleeping: bool) -> Watch {
+        Watch {
+            until,
+            sleeping,
+            wind: Wind::None,
+            temp: 0,
+            chill: Chill::Comfortable,
+        }
+    }
+
+    #[test]
+    fn only_a_plain_wait_stops_for_the_dark() {
+        assert!(!watch(None, false).expects(Interruption::Nightfall));
+        for condition in [
+            Condition::Time(TimeCat::Dawn),
+            Condition::Time(TimeCat::Noon),
+            Condition::Time(TimeCat::Night),
+            Condition::Dry,
+            Condition::Calm,
+        ] {
+            assert!(watch(Some(condition), false).expects(Interruption::Nightfall));
+            assert!(!watch(Some(condition), false).expects(Interruption::Storm));
+        }
+        assert!(watch(Some(Condition::Calm), false).expects(Interruption::Wind));
+        assert!(!watch(Some(Condition::Dry), false).expects(Interruption::Wind));
+    }
+
+    #[test]
+    fn sleepers_wake_for_storms_wind_and_cold() {
+        let sleeping = watch(None, true);
+        for cause in [
+            Interruption::Storm,
+            Interruption::Wind,
+            Interruption::Colder,
+            Interruption::Chilled,
+        ] {
+            assert!(!sleeping.expects(cause));
+        }
+        assert!(sleeping.expects(Interruption::Nightfall));
+        assert!(sleeping.expects(Interruption::Warmer));
+    }
+
+    #[test]
+    fn waiting_for_dawn_lasts_the_night() {
+        for seed in 0..8 {
+            let mut ctx = Context::with_seed(English::new(io::sink()), seed, sun::DEFAULT_LATITUDE);
+            ctx.time = Time::new(13, 0);
+            ctx.act(Action::WaitUntil(Condition::Time(TimeCat::Dawn)))
+                .unwrap();
+            assert!(!ctx.events().iter().any(|event| matches!(
+                event,
+                Event::Interrupted {
+                    cause: Interruption::Nightfall,
+                    ..
+                }
+            )));
+            if ctx
+                .events()
+                .iter()
+                .any(|event| matches!(event, Event::ConditionMet { .. }))
+            {
+                assert!(ctx.holds(Condition::Time(TimeCat::Dawn)));
+            }
+        }
+    }
+
+    #[test]
+    fn looks_around_when_the_time_of_day_changes() {
+        let mut ctx = Context::with_seed(English::new(io::sink()), 5, sun::DEFAULT_LATITUDE);
+        ctx.wind = Wind::None;
+        ctx.time = Time::new(10, 0);
+        let daylight = ctx.daylight();
+        let look = ctx.next_look() - ctx.clock();
+        let at = |mins: u64| Time::from_mins(ctx.time.mins() + mins as u16).classify(&daylight);
+        assert_eq!(at(look - 1), at(0));
+        assert_ne!(at(look), at(0));
+
+        // the weather overhead needs looking at more often in a gale
+        ctx.wind = Wind::High;
+        assert!(ctx.next_look() - ctx.clock() < look);
+    }
+
+    #[test]
+    fn far_off_ground_waits_to_be_caught_up() {
+        let mut ctx = Context::with_seed(English::new(io::sink()), 5, sun::DEFAULT_LATITUDE);
+        let start = ctx.loc.coord();
+        for _ in 0..=GROUND_RANGE {
+            ctx.act(Action::Go(Direction::East)).unwrap();
+        }
+        let (_, _, stale) = ctx.ground[&start];
+        let stale = stale.expect("out of range");
+        assert!(stale.since < ctx.clock());
+
+        ctx.act(Action::Wait(Span::from_hours(2))).unwrap();
+        assert_eq!(ctx.ground[&start].2, Some(stale));
+
+        ctx.act(Action::Go(Direction::West)).unwrap();
+        assert_eq!(ctx.ground[&start].2, None);
+        // and everything in range is kept up to date
+        assert!(ctx
+            .ground
+            .iter()
+            .all(|(coord, (_, _, stale))| stale.is_some()
+                != near(*coord, ctx.loc.coord(), GROUND_RANGE)));
+    }
+
+    #[test]
+    fn new_ground_starts_from_its_own_weather() {
+        // bare ground here, in the depths of a cold winter
+        let mut ctx = Context::with_seed(English::new(io::sink()), 5, sun::DEFAULT_LATITUDE);
+        ctx.temp = -10;
+        let here = ctx.loc.coord();
+        ctx.ground.get_mut(&here).unwrap().1 = Ground::new();
+
+        ctx.act(Action::Go(Direction::North)).unwrap();
+        assert!(ctx.ground().snow() > 10.0);
+    }
+
+    #[test]
+    fn reads_the_barometer() {
+        let mut ctx = Context::with_seed(English::new(io::sink()), 5, sun::DEFAULT_LATITUDE);
+        ctx.pressure = Pressure::new(990.0, -0.9);
+        ctx.act(Action::ReadBarometer).unwrap();
+        assert_eq!(
+            ctx.events(),
+            [Event::BarometerRead {
+                hpa: 990.0,
+                tendency: Tendency::FallingRapidly,
+            }]
+        );
+    }
+
+    #[test]
+    fn sky_odds_follow_the_dial() {
+        let mut ctx = Context::with_seed(English::new(io::sink()), 5, sun::DEFAULT_LATITUDE);
+        ctx.pressure = Pressure::new(1002.1, -1.0);
+        let (.., sky) = ctx.schedule.parts();
+        let now = ctx.clock();
+        ctx.advance_clock(now + 10).unwrap();
+        assert_eq!(Dial::classify(ctx.pressure.hpa()), Dial::Rain);
+        assert_ne!(ctx.schedule.parts().4, sky);
+
+        // a change drawn before the dial moved doesn't happen after it
+        let mut ctx = Context::with_seed(English::new(io::sink()), 5, sun::DEFAULT_LATITUDE);
+        let now = ctx.clock();
+        let later = now + 10_000;
+        ctx.pressure = Pressure::new(1002.1, -1.0);
+        ctx.sky = Sky::Clouds;
+        ctx.schedule = Schedule::from_parts(
+            later,
+            later,
+            later,
+            later,
+            [
+                (now + 30, Sky::Clear),
+                (later, Sky::Clouds),
+                (later, Sky::Rain),
+            ],
+        );
+        ctx.time_tick(31, None).unwrap();
+        assert!(!ctx.schedule.parts().4.contains(&(now + 30, Sky::Clear)));
+        assert_ne!(ctx.sky, Sky::Clear);
+    }
+
+    #[test]
+    fn loading_takes_the_biome_from_the_world() {
+        let mut ctx = Context::with_seed(English::new(io::sink()), 5, sun::DEFAULT_LATITUDE);
+        let mut save = Vec::new();
+        ctx.save(&mut save).unwrap();
+        let save = String::from_utf8(save).unwrap();
+        let loc = ctx.loc;
+        let wrong = if matches!(loc, Location::Bog(_)) {
+            "forest 0 0"
+        } else {
+            "bog 0 0"
+        };
+        let line = format!("loc {}\n", loc.store());
+        assert!(save.contains(&line));
+        let save = save.replace(&line, &format!("loc {}\n", wrong));
+
+        ctx.load(save.as_bytes()).unwrap();
+        assert_eq!(ctx.loc, loc);
+    }
+
+    #[test]
+    fn bad_save_leaves_context_untouched() {
+        let mut ctx = Context::with_seed(English::new(io::sink()), 3, sun::DEFAULT_LATITUDE);
+        let before = ctx.to_string();
+        assert!(ctx.load("not a save\n".as_bytes()).is_err());
+        assert_eq!(ctx.to_string(), before);
+    }
+}
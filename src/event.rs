@@ -0,0 +1,387 @@
+use crate::action::{Condition, Direction, Relative, Span};
+use crate::context::{Season, Sky, Time, TimeCat, Wind, DEFAULT_WAIT};
+use crate::player::{Chill, Tiredness};
+use crate::precipitation::{Kind, Precipitation};
+use crate::pressure::{Dial, Tendency};
+use crate::terrain::{Slope, Terrain};
+
+use std::io::{Error, Write};
+use std::path::PathBuf;
+
+// something that happened in the simulation. front-ends can inspect these
+// instead of parsing the narration.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Event {
+    Described(String),
+    SurroundingsChanged,
+    Moved {
+        direction: Direction,
+        relative: Option<Relative>,
+        rise: f32,
+    },
+    // there's nowhere further to go that way
+    Blocked {
+        direction: Direction,
+    },
+    SlopeChanged {
+        from: Slope,
+        to: Slope,
+    },
+    Waited {
+        span: Span,
+    },
+    WaitingUntil {
+        condition: Condition,
+    },
+    ConditionMet {
+        condition: Condition,
+        after: Span,
+    },
+    WaitAbandoned {
+        condition: Condition,
+        after: Span,
+    },
+    Interrupted {
+        cause: Interruption,
+        after: Span,
+    },
+    NotTired,
+    FellAsleep,
+    Woke {
+        after: Span,
+        cause: Option<Interruption>,
+        tiredness: Tiredness,
+    },
+    FatigueChanged {
+        from: Tiredness,
+        to: Tiredness,
+    },
+    ChillChanged {
+        from: Chill,
+        to: Chill,
+    },
+    Froze,
+    TimePassed {
+        from: Time,
+        to: Time,
+    },
+    SeasonChanged {
+        from: Season,
+        to: Season,
+    },
+    WindChanged {
+        from: Wind,
+        to: Wind,
+    },
+    // the compass direction the wind blows from
+    WindShifted {
+        from: Direction,
+        to: Direction,
+    },
+    SkyChanged {
+        from: Sky,
+        to: Sky,
+        falling: Option<Precipitation>,
+    },
+    PrecipitationChanged {
+        from: Precipitation,
+        to: Precipitation,
+    },
+    TempFelt {
+        from: i8,
+        to: i8,
+    },
+    BarometerRead {
+        hpa: f32,
+        tendency: Tendency,
+    },
+    Saved {
+        path: PathBuf,
+    },
+    SaveFailed {
+        reason: String,
+    },
+    Loaded {
+        path: PathBuf,
+    },
+    LoadFailed {
+        reason: String,
+    },
+}
+
+// something notable enough to cut a wait short
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Interruption {
+    Storm,
+    Wind,
+    Nightfall,
+    Colder,
+    Chilled, // the player, rather than the air
+    Warmer,
+}
+
+// something that wants to hear about events as they happen, such as a
+// front-end. see English for the default.
+pub trait Observer {
+    fn observe(&mut self, event: &Event) -> Result<(), Error>;
+}
+
+// narrates the events in english to a writer
+#[derive(Debug)]
+pub struct English<W: Write> {
+    w: W,
+}
+
+impl<W: Write> English<W> {
+    pub const fn new(w: W) -> Self {
+        Self { w }
+    }
+}
+
+impl<W: Write> Observer for English<W> {
+    fn observe(&mut self, event: &Event) -> Result<(), Error> {
+        render(&mut self.w, event)
+    }
+}
+
+// the default english narration
+pub fn render<W: Write>(w: &mut W, event: &Event) -> Result<(), Error> {
+    match event {
+        Event::Described(description) => write!(w, "{}", description)?,
+        Event::SurroundingsChanged => {
+            writeln!(w, "your surroundings look different.")?;
+            writeln!(w)?;
+        }
+        Event::Moved {
+            relative: Some(relative),
+            rise,
+            ..
+        } => match relative {
+            Relative::Up if *rise > Terrain::NOTICEABLE_RISE => {
+                writeln!(w, "you trudge up the slope.")?;
+            }
+            Relative::Down if *rise < -Terrain::NOTICEABLE_RISE => {
+                writeln!(w, "you make your way down the slope.")?;
+            }
+            _ => writeln!(w, "you head {}.", relative)?,
+        },
+        Event::Moved {
+            direction,
+            relative: None,
+            rise,
+        } => {
+            if *rise > Terrain::NOTICEABLE_RISE {
+                writeln!(w, "you trudge up the slope to the {}.", direction)?;
+            } else if *rise < -Terrain::NOTICEABLE_RISE {
+                writeln!(w, "you head down the slope to the {}.", direction)?;
+            } else {
+                writeln!(w, "you head {}.", direction)?;
+            }
+        }
+        Event::Blocked { direction } => {
+            writeln!(w, "you can't go any further to the {}.", direction)?;
+        }
+        Event::SlopeChanged { from, to } => {
+            if *to == Slope::Flat {
+                writeln!(w, "the ground levels out here.")?;
+            } else if to < from {
+                writeln!(w, "the terrain is less sloped here.")?;
+            } else {
+                writeln!(w, "the terrain is steeper here.")?;
+            }
+        }
+        Event::Waited { span } => {
+            if *span <= DEFAULT_WAIT {
+                writeln!(w, "some time passes.")?; // TODO: mix up time pass messages
+            } else {
+                writeln!(w, "you wait for {}.", span)?;
+            }
+        }
+        Event::WaitingUntil { condition } => {
+            writeln!(w, "you settle in to wait for {}.", condition)?
+        }
+        Event::ConditionMet { after, .. } if after.mins() == 0 => {
+            writeln!(w, "there is nothing to wait for.")?;
+        }
+        Event::ConditionMet { condition, after } => {
+            let state = match condition {
+                Condition::Time(TimeCat::Dawn) => "dawn breaks",
+                Condition::Time(TimeCat::Morning) => "it is morning",
+                Condition::Time(TimeCat::Noon) => "it is noon",
+                Condition::Time(TimeCat::Afternoon) => "it is afternoon",
+                Condition::Time(TimeCat::Dusk) => "dusk falls",
+                Condition::Time(TimeCat::Night) => "night falls",
+                Condition::Dry => "the rain has stopped",
+                Condition::Calm => "the wind has died down",
+            };
+            writeln!(w, "after {}, {}.", after, state)?;
+        }
+        Event::WaitAbandoned { condition, after } => {
+            writeln!(w, "you give up waiting for {} after {}.", condition, after)?;
+        }
+        Event::Interrupted { cause, after } => {
+            let reason = match cause {
+                Interruption::Storm => "the weather is turning",
+                Interruption::Wind => "the wind is picking up",
+                Interruption::Nightfall => "night is falling",
+                Interruption::Colder => "it has grown much colder",
+                Interruption::Chilled => "you are getting too cold to keep still",
+                Interruption::Warmer => "it has grown much warmer",
+            };
+            writeln!(w, "{}, so you stop waiting after {}.", reason, after)?;
+        }
+        Event::NotTired => writeln!(w, "you aren't tired enough to sleep.")?,
+        Event::FellAsleep => writeln!(w, "you lie down and fall asleep.")?,
+        Event::Woke {
+            after,
+            cause,
+            tiredness,
+        } => {
+            match cause {
+                None => writeln!(w, "you wake up after {}.", after)?,
+                Some(cause) => {
+                    let reason = match cause {
+                        Interruption::Storm => "the weather",
+                        Interruption::Wind => "the rising wind",
+                        // nothing else wakes a sleeper
+                        _ => "the cold",
+                    };
+                    writeln!(w, "{} wakes you after {}.", reason, after)?;
+                }
+            }
+            if *tiredness == Tiredness::Rested {
+                writeln!(w, "you feel rested.")?;
+            } else {
+                writeln!(w, "you still feel {}.", tiredness)?;
+            }
+            writeln!(w)?;
+        }
+        Event::FatigueChanged { to, .. } => match to {
+            Tiredness::Rested => writeln!(w, "you feel rested.")?,
+            Tiredness::Weary => writeln!(w, "you are starting to feel weary.")?,
+            Tiredness::Tired => writeln!(w, "you feel tired.")?,
+            Tiredness::Exhausted => writeln!(w, "you are exhausted.")?,
+        },
+        Event::ChillChanged { from, to } if to > from => match to {
+            Chill::Comfortable => (),
+            Chill::Cold => writeln!(w, "you are getting cold.")?,
+            Chill::Shivering => writeln!(w, "you are shivering.")?,
+            Chill::Numb => writeln!(
+                w,
+                "you are shivering violently and can't feel your fingers."
+            )?,
+            Chill::Failing => {
+                writeln!(
+                    w,
+                    "you have stopped shivering. a strange calm comes over you."
+                )?;
+            }
+            Chill::Frozen => (), // see Froze
+        },
+        Event::ChillChanged { to, .. } => match to {
+            Chill::Comfortable => writeln!(w, "you feel warm again.")?,
+            _ => writeln!(w, "you feel a little warmer.")?,
+        },
+        Event::Froze => {
+            writeln!(w, "you sink down into the cold and do not get up again.")?;
+            writeln!(w)?;
+            writeln!(w, "you have died of exposure.")?;
+        }
+        Event::TimePassed { .. } => (),
+        Event::SeasonChanged { to, .. } => {
+            let season = match to {
+                Season::Spring => "spring",
+                Season::Summer => "summer",
+                Season::Autumn => "autumn",
+                Season::Winter => "winter",
+            };
+            writeln!(w, "you sense that {} has arrived.", season)?;
+        }
+
+        Event::WindChanged { from, to } => {
+            if to > from {
+                writeln!(w, "the wind speeds up.")?;
+            } else if to < from {
+                writeln!(w, "the wind slows down.")?;
+            }
+        }
+
+        Event::WindShifted { to, .. } => {
+            writeln!(w, "the wind shifts and blows from the {} now.", to)?;
+        }
+
+        Event::SkyChanged { from, to, falling } => match (from, to, falling) {
+            (Sky::Clear, Sky::Clear, _) => (),
+            (Sky::Clouds, Sky::Clouds, _) => (),
+            (Sky::Rain, Sky::Rain, _) => (),
+
+            (Sky::Clear, Sky::Clouds, _) => writeln!(w, "it gets cloudy.")?,
+            (Sky::Clear | Sky::Clouds, Sky::Rain, Some(falling)) if falling.thunder => {
+                writeln!(w, "a thunderstorm breaks.")?;
+            }
+            (Sky::Clear | Sky::Clouds, Sky::Rain, Some(falling)) => match falling.kind {
+                Kind::Rain => writeln!(w, "it starts raining.")?,
+                Kind::Sleet => writeln!(w, "it starts to sleet.")?,
+                Kind::FreezingRain => writeln!(w, "a freezing rain begins to fall.")?,
+                Kind::Snow => writeln!(w, "it starts snowing.")?,
+                Kind::Hail => writeln!(w, "hail starts to fall.")?,
+            },
+            (Sky::Clear | Sky::Clouds, Sky::Rain, None) => writeln!(w, "it starts raining.")?,
+            (Sky::Clouds | Sky::Rain, Sky::Clear, _) => writeln!(w, "the sky clears up.")?,
+            (Sky::Rain, Sky::Clouds, Some(falling)) if falling.thunder => {
+                writeln!(w, "the storm passes.")?;
+            }
+            (Sky::Rain, Sky::Clouds, Some(falling)) => match falling.kind {
+                Kind::Rain => writeln!(w, "it stops raining.")?,
+                Kind::Sleet => writeln!(w, "it stops sleeting.")?,
+                Kind::Snow => writeln!(w, "it stops snowing.")?,
+                kind @ (Kind::FreezingRain | Kind::Hail) => writeln!(w, "the {} stops.", kind)?,
+            },
+            (Sky::Rain, Sky::Clouds, None) => writeln!(w, "it stops raining.")?,
+        },
+
+        Event::PrecipitationChanged { from, to } => {
+            if to.kind != from.kind {
+                writeln!(w, "the {} turns to {}.", from.kind, to.kind)?;
+            } else if to.intensity > from.intensity {
+                writeln!(w, "the {} is getting heavier.", to.kind)?;
+            } else if to.intensity < from.intensity {
+                writeln!(w, "the {} is easing off.", to.kind)?;
+            }
+
+            if to.thunder && !from.thunder {
+                writeln!(w, "thunder rumbles overhead.")?;
+            } else if from.thunder && !to.thunder {
+                writeln!(w, "the thunder dies away.")?;
+            }
+        }
+
+        Event::TempFelt { from, to } => {
+            if to < from {
+                writeln!(w, "it feels colder.")?;
+            } else if to > from {
+                writeln!(w, "it feels warmer.")?;
+            }
+        }
+
+        Event::BarometerRead { hpa, tendency } => {
+            writeln!(
+                w,
+                "the barometer reads {:.0} hPa, with the needle at \"{}\".",
+                hpa,
+                Dial::classify(*hpa)
+            )?;
+            writeln!(w, "the pressure is {}.", tendency)?;
+        }
+
+        Event::Saved { path } => writeln!(w, "saved to {}.", path.display())?,
+        Event::SaveFailed { reason } => writeln!(w, "could not save: {}", reason)?,
+        Event::Loaded { path } => {
+            writeln!(w, "loaded {}.", path.display())?;
+            writeln!(w)?;
+        }
+        Event::LoadFailed { reason } => writeln!(w, "could not load: {}", reason)?,
+    }
+
+    Ok(())
+}
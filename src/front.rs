@@ -0,0 +1,139 @@
+use crate::action::Direction;
+use crate::context::{Coord, Sky, Wind};
+
+// a weather system sitting over part of the world. storms bring cloud and
+// rain, clear fronts push them away. both drift downwind, so they can be seen
+// coming, walked into or walked away from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Front {
+    kind: Kind,
+    n: f32, // centre, in coords
+    w: f32,
+    radius: f32,
+    until: u64, // when it blows itself out (see Context::clock)
+}
+
+impl Front {
+    // the inner part of a front has the weather settled, the outer part only
+    // leans that way
+    const CORE: f32 = 0.5; // of the radius
+
+    // how far past its edge a front can be made out, in coords
+    pub const SIGHT: f32 = 30.0;
+    // fronts crawl along far slower than the wind itself, so that a walker
+    // can keep ahead of one. coords per hour, per km/h of wind.
+    pub const DRIFT: f32 = 0.2;
+
+    pub const fn new(kind: Kind, n: f32, w: f32, radius: f32, until: u64) -> Self {
+        Self {
+            kind,
+            n,
+            w,
+            radius,
+            until,
+        }
+    }
+
+    // used when saving
+    pub const fn parts(&self) -> (Kind, f32, f32, f32, u64) {
+        (self.kind, self.n, self.w, self.radius, self.until)
+    }
+
+    pub const fn kind(&self) -> Kind {
+        self.kind
+    }
+
+    pub const fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    pub const fn until(&self) -> u64 {
+        self.until
+    }
+
+    // from the centre
+    pub fn distance(&self, coord: Coord) -> f32 {
+        (self.n - f32::from(coord.n)).hypot(self.w - f32::from(coord.w))
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        (self.n - other.n).hypot(self.w - other.w) < self.radius + other.radius
+    }
+
+    // how far off its edge is, or 0 if it's already overhead
+    pub fn distance_to_edge(&self, coord: Coord) -> f32 {
+        (self.distance(coord) - self.radius).max(0.0)
+    }
+
+    pub fn cover(&self, coord: Coord) -> Option<Cover> {
+        let distance = self.distance(coord);
+        if distance <= self.radius * Self::CORE {
+            Some(Cover::Core(self.kind))
+        } else if distance <= self.radius {
+            Some(Cover::Edge(self.kind))
+        } else {
+            None
+        }
+    }
+
+    // the compass direction it lies in, seen from `coord`
+    pub fn bearing(&self, coord: Coord) -> Direction {
+        let (n, w) = (self.n - f32::from(coord.n), self.w - f32::from(coord.w));
+        if n.abs() >= w.abs() {
+            if n >= 0.0 {
+                Direction::North
+            } else {
+                Direction::South
+            }
+        } else if w >= 0.0 {
+            Direction::West
+        } else {
+            Direction::East
+        }
+    }
+
+    // carried along for `mins` minutes by a wind blowing from `from`
+    pub fn drift(&mut self, mins: u64, wind: Wind, from: Direction) {
+        let distance = wind.speed() * Self::DRIFT * mins as f32 / 60.0;
+        let step = from.opposite().as_coord_with_magnitude(1);
+        self.n += f32::from(step.n) * distance;
+        self.w += f32::from(step.w) * distance;
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Kind {
+    Storm,
+    Clear,
+}
+
+// how a front sits over one place
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Cover {
+    Core(Kind),
+    Edge(Kind),
+}
+
+impl Cover {
+    // whether the sky can turn this way under the front
+    pub const fn admits(&self, sky: Sky) -> bool {
+        match self {
+            Self::Core(Kind::Storm) => matches!(sky, Sky::Rain),
+            Self::Edge(Kind::Storm) => !matches!(sky, Sky::Clear),
+            Self::Core(Kind::Clear) => matches!(sky, Sky::Clear),
+            Self::Edge(Kind::Clear) => !matches!(sky, Sky::Rain),
+        }
+    }
+
+    // the sky arriving under the front turns into
+    pub const fn settle(&self, sky: Sky) -> Sky {
+        if self.admits(sky) {
+            return sky;
+        }
+        match self {
+            Self::Core(Kind::Storm) => Sky::Rain,
+            Self::Core(Kind::Clear) => Sky::Clear,
+            Self::Edge(_) => Sky::Clouds,
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use crate::context::{Season, Sky, Wind};
+use crate::precipitation::{Intensity, Kind, Precipitation};
+
+use std::fmt::{self, Display, Formatter};
+
+// what's lying on the ground in one place. it builds up and wears away with
+// the weather, so unlike the terrain it has to be kept track of.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Ground {
+    snow: f32,   // depth, cm
+    ice: f32,    // thickness, mm
+    mud: f32,    // 0 is dry, 1 is waterlogged
+    leaves: f32, // fallen leaf cover, 0 to 1
+}
+
+impl Ground {
+    // per hour
+    const SNOWFALL: [f32; 3] = [0.2, 0.5, 1.0]; // cm, by intensity
+    const FREEZING_RAIN: [f32; 3] = [1.0, 2.0, 4.0]; // mm
+    const RAIN_SOAK: [f32; 3] = [0.05, 0.1, 0.2];
+    const SNOW_MELT: f32 = 0.15; // cm per degree above freezing
+    const SUN_MELT: f32 = 0.5; // cm in full sun
+    const RAIN_MELT: f32 = 0.3; // cm
+    const ICE_MELT: f32 = 0.5; // mm per degree above freezing
+    const SETTLING: f32 = 0.002; // of the depth, as the snow packs down
+    const DRYING: f32 = 0.02;
+    const WARM_DRYING: f32 = 0.002; // per degree
+    const SUN_DRYING: f32 = 0.03;
+    const LEAF_FALL: f32 = 0.01; // of what's left on the trees
+    const LEAF_ROT: f32 = 0.002;
+    // how much of the snow the branches catch under full cover
+    const INTERCEPTION: f32 = 0.3;
+
+    pub const fn new() -> Self {
+        Self {
+            snow: 0.0,
+            ice: 0.0,
+            mud: 0.0,
+            leaves: 0.0,
+        }
+    }
+
+    // a plausible state to start out with, when there's no history yet
+    pub fn seasonal(season: Season, temp: i8, trees: f32) -> Self {
+        let mut ground = Self::new();
+        match season {
+            Season::Winter if temp <= 0 => ground.snow = 30.0 * (1.0 - trees * Self::INTERCEPTION),
+            Season::Winter | Season::Spring => ground.mud = 0.4,
+            Season::Summer => (),
+            Season::Autumn => ground.leaves = trees * 0.6,
+        }
+        ground
+    }
+
+    // used when restoring a save
+    pub const fn from_parts(snow: f32, ice: f32, mud: f32, leaves: f32) -> Self {
+        Self {
+            snow,
+            ice,
+            mud,
+            leaves,
+        }
+    }
+
+    pub const fn parts(&self) -> (f32, f32, f32, f32) {
+        (self.snow, self.ice, self.mud, self.leaves)
+    }
+
+    pub const fn snow(&self) -> f32 {
+        self.snow
+    }
+
+    pub fn is_icy(&self) -> bool {
+        self.ice >= 1.0
+    }
+
+    // spend `mins` minutes in unchanging weather
+    pub fn pass(&mut self, mins: u64, weather: Weathering) {
+        let hours = mins as f32 / 60.0;
+        let temp = f32::from(weather.temp);
+        let shelter = 1.0 - weather.trees * Self::INTERCEPTION;
+
+        // what lands
+        if let Some(falling) = weather.falling {
+            let rate = |rates: [f32; 3]| {
+                rates[match falling.intensity {
+                    Intensity::Light => 0,
+                    Intensity::Moderate => 1,
+                    Intensity::Heavy => 2,
+                }] * hours
+            };
+            match falling.kind {
+                Kind::Snow => self.snow += rate(Self::SNOWFALL) * shelter,
+                Kind::Hail => self.snow += rate(Self::SNOWFALL) * shelter * 0.3,
+                Kind::FreezingRain => self.ice += rate(Self::FREEZING_RAIN),
+                Kind::Rain | Kind::Sleet => {
+                    self.mud += rate(Self::RAIN_SOAK);
+                    if self.snow > 0.0 {
+                        self.snow -= Self::RAIN_MELT * hours;
+                    }
+                }
+            }
+        }
+
+        // what melts. the sun can get at snow a little below freezing
+        let mut melt = temp.max(0.0) * Self::SNOW_MELT;
+        if temp > -2.0 {
+            melt += weather.sunlight * Self::SUN_MELT;
+        }
+        let melted = (melt * hours).min(self.snow.max(0.0));
+        self.snow -= melted + self.snow * Self::SETTLING * hours;
+        self.mud += melted * 0.02; // meltwater soaks in
+        self.ice -= temp.max(0.0) * Self::ICE_MELT * hours;
+
+        // puddles freeze over, and the ground dries when it can
+        let bare = self.snow < 1.0;
+        if bare && temp < -1.0 && self.mud > 0.5 {
+            self.ice = self.ice.max(1.0);
+        }
+        if bare && temp > 0.0 && weather.falling.is_none() {
+            let drying =
+                Self::DRYING + temp * Self::WARM_DRYING + weather.sunlight * Self::SUN_DRYING;
+            self.mud -= drying * hours;
+        }
+
+        // the trees drop their leaves in autumn, and they rot away after
+        if weather.season == Season::Autumn {
+            self.leaves += (weather.trees - self.leaves).max(0.0) * Self::LEAF_FALL * hours;
+        } else {
+            self.leaves -= Self::LEAF_ROT * hours;
+        }
+
+        self.snow = self.snow.max(0.0);
+        self.ice = self.ice.max(0.0);
+        self.mud = self.mud.clamp(0.0, 1.0);
+        self.leaves = self.leaves.clamp(0.0, 1.0);
+    }
+}
+
+// what the ground is exposed to
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Weathering {
+    pub temp: i8,
+    pub falling: Option<Precipitation>,
+    pub sunlight: f32, // as given by Location::sunlight
+    pub trees: f32,    // as given by Location::trees
+    pub season: Season,
+}
+
+// only the ground near the player is kept up to date. this is the weather
+// some ground further off was left in, so that it can be caught up when the
+// player comes back, as if that weather had held.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stale {
+    pub since: u64, // see Context::clock
+    pub sky: Sky,   // there, as given by Context::sky_at
+    pub temp: i8,   // likewise
+    pub wind: Wind, // over the region
+}
+
+// one sentence per line, or nothing at all for bare ground
+impl Display for Ground {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        if self.snow >= 30.0 {
+            writeln!(f, "thick snow covers the ground.")?;
+        } else if self.snow >= 10.0 {
+            writeln!(f, "snow covers the ground.")?;
+        } else if self.snow >= 1.0 {
+            writeln!(f, "a thin layer of snow lies on the ground.")?;
+        } else if self.snow >= 0.2 {
+            writeln!(f, "there is a dusting of snow on the ground.")?;
+        }
+        let bare = self.snow < 1.0;
+
+        if self.is_icy() {
+            if bare {
+                writeln!(f, "the ground is glazed with ice.")?;
+            } else {
+                writeln!(f, "there is ice under the snow.")?;
+            }
+        } else if bare && self.mud >= 0.6 {
+            writeln!(f, "the ground is wet and muddy.")?;
+        } else if bare && self.mud >= 0.3 {
+            writeln!(f, "the ground is damp.")?;
+        }
+
+        if bare && self.leaves >= 0.3 {
+            if self.mud < 0.3 && !self.is_icy() {
+                writeln!(f, "dry leaves crunch underfoot.")?;
+            } else {
+                writeln!(f, "sodden leaves cover the ground.")?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,27 +1,34 @@
 use std::fmt::Display;
-use std::io::{self, Error, Write};
+use std::io::{self, Error, ErrorKind, Write};
 
-pub fn menu<'a, W: Write, C: Display>(w: &mut W, choices: &'a [C]) -> Result<&'a C, Error> {
+// the letter typed to pick a menu entry
+pub trait Key {
+    fn key(&self) -> char;
+}
+
+pub fn menu<'a, W: Write, C: Display + Key>(w: &mut W, choices: &'a [C]) -> Result<&'a C, Error> {
     writeln!(w)?;
 
     loop {
-        for (n, choice) in choices.iter().enumerate() {
-            writeln!(w, " {}) {}", n, choice)?;
+        for choice in choices {
+            writeln!(w, " {}) {}", choice.key(), choice)?;
         }
         writeln!(w)?;
         w.flush()?;
 
-        match readln(w, "? ")?.parse::<usize>() {
-            Ok(index) => {
-                w.flush()?;
-                if let Some(chosen) = choices.get(index) {
+        let input = readln(w, "? ")?;
+        w.flush()?;
+        let mut chars = input.chars();
+        match (chars.next(), chars.next()) {
+            (Some(key), None) => {
+                if let Some(chosen) = choices.iter().find(|choice| choice.key() == key) {
                     writeln!(w)?;
                     return Ok(chosen);
                 } else {
                     writeln!(w, "no such choice\n")?;
                 }
             }
-            Err(err) => writeln!(w, "{}\n", err)?,
+            _ => writeln!(w, "type one of the letters\n")?,
         }
     }
 }
@@ -33,7 +40,9 @@ pub fn readln(w: &mut impl Write, prompt: impl Display) -> Result<String, Error>
     write!(w, "{}", prompt)?;
     w.flush()?;
 
-    stdin.read_line(&mut input)?;
+    if stdin.read_line(&mut input)? == 0 {
+        return Err(Error::new(ErrorKind::UnexpectedEof, "end of input"));
+    }
 
     Ok(input.trim().escape_default().to_string())
 }
@@ -1,3 +1,16 @@
 pub mod action;
 pub mod context;
-pub mod input;
+pub mod event;
+pub mod front;
+pub mod ground;
+pub mod noise;
+pub mod player;
+pub mod precipitation;
+pub mod pressure;
+pub mod rng;
+pub mod save;
+pub mod schedule;
+pub mod sun;
+pub mod terrain;
+pub mod weather;
+pub mod world;
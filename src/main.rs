@@ -1,31 +1,260 @@
-use lib::action::Action;
-use lib::context::Context;
-use lib::input;
+mod input;
 
-use std::io::{self, Error};
+use input::Key;
+use lib::action::{Action, Condition, Direction, Relative, Span};
+use lib::context::{Context, Navigation, TimeCat};
+use lib::event::English;
+use lib::sun;
+
+use std::env;
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, Error, ErrorKind, Write};
+use std::path::PathBuf;
 use std::process;
 
 fn main() {
     if let Err(err) = try_main() {
+        if err.kind() == ErrorKind::UnexpectedEof {
+            return;
+        }
         eprintln!("fatal: {}", err);
         process::exit(1);
     }
 }
 
 fn try_main() -> Result<(), Error> {
+    let args = Args::parse()?;
+
     let mut out = io::stdout();
-    let mut ctx = Context::spawn(io::stdout());
+    let latitude = args.latitude.unwrap_or(sun::DEFAULT_LATITUDE);
+    let mut ctx = match args.seed {
+        Some(seed) => Context::with_seed(English::new(io::stdout()), seed, latitude),
+        None => Context::spawn(English::new(io::stdout()), latitude),
+    };
+    if let Some(path) = args.save_path {
+        ctx.set_save_path(path);
+    }
+    if args.relative {
+        ctx.set_navigation(Navigation::Relative);
+    }
+    if args.survival {
+        ctx.set_survival(true);
+    }
+
+    // so that a session can be reported and played back with --seed
+    if args.show_seed {
+        eprintln!("seed {}", ctx.seed());
+    }
 
     ctx.act(Action::Describe)?;
 
     loop {
-        let actions = ctx.available_actions();
-        let action = input::menu(&mut out, &actions)?;
+        let action = prompt_action(&mut out, &ctx.available_actions())?;
 
-        if ctx.act(*action)? {
+        if ctx.act(action)? {
             break;
         }
     }
 
     Ok(())
 }
+
+// every direction is its own action, but the menu only shows a single "go"
+// entry and asks for the direction afterwards. waiting for something works the
+// same way.
+#[derive(Clone, Copy, PartialEq)]
+enum Choice {
+    Act(Action),
+    Go,
+    WaitFor,
+    Way(Action),
+    Cancel,
+}
+
+impl Display for Choice {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::Way(Action::Go(direction)) => write!(f, "{}", direction),
+            Self::Way(Action::GoRelative(relative)) => write!(f, "{}", relative),
+            Self::Way(Action::WaitUntil(condition)) => write!(f, "{}", condition),
+            Self::Act(Action::Wait(_)) => write!(f, "wait"),
+            Self::Act(action) | Self::Way(action) => write!(f, "{}", action),
+            Self::Go => write!(f, "go"),
+            Self::WaitFor => write!(f, "wait for..."),
+            Self::Cancel => write!(f, "cancel"),
+        }
+    }
+}
+
+impl Key for Choice {
+    fn key(&self) -> char {
+        match self {
+            Self::Act(action) | Self::Way(action) => match action {
+                Action::Describe => 'd',
+                Action::Go(Direction::North) => 'n',
+                Action::Go(Direction::South) => 's',
+                Action::Go(Direction::East) => 'e',
+                Action::Go(Direction::West) => 'w',
+                // vi keys, with "up" being up the slope
+                Action::GoRelative(Relative::Up) => 'k',
+                Action::GoRelative(Relative::Down) => 'j',
+                Action::GoRelative(Relative::Left) => 'h',
+                Action::GoRelative(Relative::Right) => 'l',
+                Action::Wait(_) => 'w',
+                Action::WaitUntil(condition) => match condition {
+                    Condition::Time(TimeCat::Dawn) => 'd',
+                    Condition::Time(TimeCat::Morning) => 'm',
+                    Condition::Time(TimeCat::Noon) => 'o',
+                    Condition::Time(TimeCat::Afternoon) => 'a',
+                    Condition::Time(TimeCat::Dusk) => 'u',
+                    Condition::Time(TimeCat::Night) => 'n',
+                    Condition::Dry => 'r',
+                    Condition::Calm => 'w',
+                },
+                Action::Sleep => 'z',
+                Action::ReadBarometer => 'b',
+                Action::Save => 's',
+                Action::Load => 'l',
+                Action::Quit => 'q',
+            },
+            Self::Go => 'g',
+            Self::WaitFor => 'f',
+            Self::Cancel => 'c',
+        }
+    }
+}
+
+fn prompt_action<W: Write>(w: &mut W, actions: &[Action]) -> Result<Action, Error> {
+    let mut choices = Vec::new();
+    let mut ways = vec![Choice::Cancel];
+    let mut conditions = vec![Choice::Cancel];
+    for action in actions {
+        match action {
+            Action::Go(_) | Action::GoRelative(_) => {
+                if !choices.contains(&Choice::Go) {
+                    choices.push(Choice::Go);
+                }
+                ways.push(Choice::Way(*action));
+            }
+            Action::WaitUntil(_) => {
+                if !choices.contains(&Choice::WaitFor) {
+                    choices.push(Choice::WaitFor);
+                }
+                conditions.push(Choice::Way(*action));
+            }
+            _ => choices.push(Choice::Act(*action)),
+        }
+    }
+
+    loop {
+        match input::menu(w, &choices)? {
+            Choice::Act(Action::Wait(default)) => return prompt_wait(w, *default),
+            Choice::Act(action) => return Ok(*action),
+            Choice::Go => {
+                writeln!(w, "where to go?")?;
+                match input::menu(w, &ways)? {
+                    Choice::Way(action) => return Ok(*action),
+                    _ => continue,
+                }
+            }
+            Choice::WaitFor => {
+                writeln!(w, "wait for what?")?;
+                match input::menu(w, &conditions)? {
+                    Choice::Way(action) => return Ok(*action),
+                    _ => continue,
+                }
+            }
+            Choice::Way(_) | Choice::Cancel => unreachable!("not in the main menu"),
+        }
+    }
+}
+
+fn prompt_wait<W: Write>(w: &mut W, default: Span) -> Result<Action, Error> {
+    loop {
+        let answer = input::readln(w, format!("how long? [{}] ", default))?;
+        if answer.is_empty() {
+            writeln!(w)?;
+            return Ok(Action::Wait(default));
+        }
+
+        match answer.parse::<Span>() {
+            Ok(span) => {
+                writeln!(w)?;
+                return Ok(Action::Wait(span));
+            }
+            Err(err) => writeln!(w, "{}\n", err)?,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Args {
+    seed: Option<u64>,
+    save_path: Option<PathBuf>,
+    latitude: Option<f32>,
+    relative: bool,
+    survival: bool,
+    show_seed: bool,
+}
+
+impl Args {
+    fn parse() -> Result<Self, Error> {
+        let mut parsed = Self::default();
+        let mut args = env::args().skip(1);
+
+        while let Some(arg) = args.next() {
+            // accept both "--flag value" and "--flag=value"
+            let (flag, value) = match arg.split_once('=') {
+                Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            };
+            match flag.as_str() {
+                "--relative" => {
+                    parsed.relative = true;
+                    continue;
+                }
+                "--survival" => {
+                    parsed.survival = true;
+                    continue;
+                }
+                "--show-seed" => {
+                    parsed.show_seed = true;
+                    continue;
+                }
+                _ => (),
+            }
+            let value = || {
+                value
+                    .or_else(|| args.next())
+                    .ok_or_else(|| invalid_arg(format!("{} needs a value", flag)))
+            };
+
+            match flag.as_str() {
+                "--seed" => {
+                    let value = value()?;
+                    let seed = value
+                        .parse()
+                        .map_err(|err| invalid_arg(format!("bad seed '{}': {}", value, err)))?;
+                    parsed.seed = Some(seed);
+                }
+                "--save" => parsed.save_path = Some(PathBuf::from(value()?)),
+                "--latitude" => {
+                    let value = value()?;
+                    let latitude = value
+                        .parse::<f32>()
+                        .ok()
+                        .filter(|latitude| (-90.0..=90.0).contains(latitude))
+                        .ok_or_else(|| invalid_arg(format!("bad latitude '{}'", value)))?;
+                    parsed.latitude = Some(latitude);
+                }
+                _ => return Err(invalid_arg(format!("unknown argument '{}'", flag))),
+            }
+        }
+
+        Ok(parsed)
+    }
+}
+
+fn invalid_arg(msg: String) -> Error {
+    Error::new(ErrorKind::InvalidInput, msg)
+}
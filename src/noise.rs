@@ -0,0 +1,56 @@
+// deterministic value noise. the same seed and position always give the same
+// value, so nothing generated from it ever needs to be stored.
+
+pub fn hash(seed: u64, x: i32, y: i32) -> u64 {
+    // splitmix64 finalizer over the packed inputs
+    let mut z = seed
+        ^ (x as u32 as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15)
+        ^ (y as u32 as u64)
+            .wrapping_mul(0xc2b2_ae3d_27d4_eb4f)
+            .rotate_left(32);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+// in [0, 1)
+pub fn unit(seed: u64, x: i32, y: i32) -> f32 {
+    (hash(seed, x, y) >> 40) as f32 / (1_u64 << 24) as f32
+}
+
+// smoothly interpolated lattice noise in [0, 1)
+pub fn value(seed: u64, x: f32, y: f32) -> f32 {
+    let (x0, y0) = (x.floor(), y.floor());
+    let (tx, ty) = (smoothstep(x - x0), smoothstep(y - y0));
+    let (x0, y0) = (x0 as i32, y0 as i32);
+
+    let top = lerp(unit(seed, x0, y0), unit(seed, x0 + 1, y0), tx);
+    let bottom = lerp(unit(seed, x0, y0 + 1), unit(seed, x0 + 1, y0 + 1), tx);
+    lerp(top, bottom, ty)
+}
+
+// several octaves of value noise, each half the scale and weight of the last.
+// still in [0, 1).
+pub fn fractal(seed: u64, x: f32, y: f32, scale: f32, octaves: u32) -> f32 {
+    let mut total = 0.0;
+    let mut weight = 1.0;
+    let mut weights = 0.0;
+    let mut scale = scale;
+
+    for octave in 0..octaves {
+        total += value(seed.wrapping_add(octave.into()), x / scale, y / scale) * weight;
+        weights += weight;
+        weight *= 0.5;
+        scale *= 0.5;
+    }
+
+    total / weights
+}
+
+fn smoothstep(t: f32) -> f32 {
+    t * t * (3.0 - 2.0 * t)
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
@@ -0,0 +1,249 @@
+use crate::context::{self, Wind};
+use crate::precipitation::{Kind, Precipitation};
+
+use std::fmt::{self, Display, Formatter};
+
+// the state of the person wandering around
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Player {
+    fatigue: f32, // 0 is fully rested, 1 is dead on your feet
+    body: f32,    // core temperature, celcius
+    wetness: f32, // 0 is dry, 1 is soaked through
+    asleep: bool,
+    mortal: bool, // whether the cold can kill
+}
+
+impl Player {
+    // a full day awake wears you out
+    const AWAKE_FATIGUE: f32 = 1.0 / (18.0 * 60.0);
+    // and a night's sleep puts it right
+    const SLEEP_RECOVERY: f32 = 1.0 / (8.0 * 60.0);
+    const GO_FATIGUE: f32 = 0.01;
+    const CLIMB_FATIGUE: f32 = 0.0005; // per metre climbed
+    const SLEEPY: f32 = 0.25;
+
+    pub const NORMAL_BODY: f32 = 37.0;
+    pub const FROZEN_BODY: f32 = 28.0;
+    // without survival mode the cold numbs but never kills
+    pub const HARDY_BODY: f32 = 30.5;
+    // clothing keeps you comfortable down to about this, once wind and wet
+    // are taken into account
+    const COMFORT: f32 = 5.0;
+    const COOLING: f32 = 0.0006; // per minute, per degree below comfort
+    const WARMING: f32 = 0.015; // per minute
+    const GO_WARMTH: f32 = 0.05; // walking keeps you warm
+    const WET_CHILL: f32 = 8.0; // degrees, when soaked
+    const RAIN_WETTING: f32 = 0.01; // per minute
+    const SLEET_WETTING: f32 = 0.006;
+    const SNOW_WETTING: f32 = 0.002;
+    const DRYING: f32 = 0.003;
+
+    pub const fn new() -> Self {
+        Self {
+            fatigue: 0.0,
+            body: Self::NORMAL_BODY,
+            wetness: 0.0,
+            asleep: false,
+            mortal: false,
+        }
+    }
+
+    // used when restoring a save
+    pub const fn from_parts(fatigue: f32, body: f32, wetness: f32, mortal: bool) -> Self {
+        Self {
+            fatigue,
+            body,
+            wetness,
+            asleep: false,
+            mortal,
+        }
+    }
+
+    pub const fn fatigue(&self) -> f32 {
+        self.fatigue
+    }
+
+    pub fn tiredness(&self) -> Tiredness {
+        Tiredness::classify(self.fatigue)
+    }
+
+    pub const fn body(&self) -> f32 {
+        self.body
+    }
+
+    pub fn chill(&self) -> Chill {
+        Chill::classify(self.body)
+    }
+
+    pub const fn wetness(&self) -> f32 {
+        self.wetness
+    }
+
+    pub const fn is_mortal(&self) -> bool {
+        self.mortal
+    }
+
+    pub fn set_mortal(&mut self, mortal: bool) {
+        self.mortal = mortal;
+    }
+
+    pub const fn is_asleep(&self) -> bool {
+        self.asleep
+    }
+
+    pub fn fall_asleep(&mut self) {
+        self.asleep = true;
+    }
+
+    pub fn wake(&mut self) {
+        self.asleep = false;
+    }
+
+    pub fn can_sleep(&self) -> bool {
+        self.fatigue >= Self::SLEEPY
+    }
+
+    // how long a sleep it would take to be fully rested
+    pub fn sleep_needed(&self) -> u32 {
+        (self.fatigue / Self::SLEEP_RECOVERY).ceil() as u32
+    }
+
+    // walking one coord, climbing `rise` metres on the way
+    pub fn travel(&mut self, rise: f32) {
+        self.add_fatigue(Self::GO_FATIGUE + rise.max(0.0) * Self::CLIMB_FATIGUE);
+        self.body = (self.body + Self::GO_WARMTH).min(Self::NORMAL_BODY);
+    }
+
+    // spend `mins` minutes out in unchanging weather
+    pub fn pass(&mut self, mins: u64, weather: Exposure) {
+        let minutes = mins as f32;
+        if self.asleep {
+            self.add_fatigue(-minutes * Self::SLEEP_RECOVERY);
+        } else {
+            self.add_fatigue(minutes * Self::AWAKE_FATIGUE);
+        }
+
+        // wetness and warmth depend on each other, so go a minute at a time
+        let floor = if self.mortal {
+            Self::FROZEN_BODY
+        } else {
+            Self::HARDY_BODY
+        };
+        for _ in 0..mins {
+            let wetting = match weather.falling.map(|falling| falling.kind) {
+                Some(Kind::Rain | Kind::FreezingRain) => Self::RAIN_WETTING,
+                Some(Kind::Sleet) => Self::SLEET_WETTING,
+                Some(Kind::Snow | Kind::Hail) => Self::SNOW_WETTING,
+                None => -Self::DRYING,
+            };
+            self.wetness = (self.wetness + wetting).clamp(0.0, 1.0);
+
+            let felt = weather.felt() - self.wetness * Self::WET_CHILL;
+            if felt < Self::COMFORT {
+                self.body -= (Self::COMFORT - felt) * Self::COOLING;
+            } else {
+                self.body += Self::WARMING;
+            }
+            self.body = self.body.clamp(floor, Self::NORMAL_BODY);
+
+            if self.is_frozen() {
+                break;
+            }
+        }
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.body <= Self::FROZEN_BODY
+    }
+
+    fn add_fatigue(&mut self, amount: f32) {
+        self.fatigue = (self.fatigue + amount).clamp(0.0, 1.0);
+    }
+}
+
+impl Default for Player {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// the weather the player is out in
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Exposure {
+    pub temp: i8,
+    pub wind: Wind,
+    pub falling: Option<Precipitation>,
+}
+
+impl Exposure {
+    // how cold it is on the skin, before getting wet
+    fn felt(&self) -> f32 {
+        context::apparent_temp(f32::from(self.temp), self.wind, false)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub enum Tiredness {
+    Rested,
+    Weary,
+    Tired,
+    Exhausted,
+}
+
+impl Tiredness {
+    pub fn classify(fatigue: f32) -> Self {
+        if fatigue < 0.6 {
+            Self::Rested
+        } else if fatigue < 0.8 {
+            Self::Weary
+        } else if fatigue < 0.95 {
+            Self::Tired
+        } else {
+            Self::Exhausted
+        }
+    }
+}
+
+impl Display for Tiredness {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Rested => "rested",
+                Self::Weary => "weary",
+                Self::Tired => "tired",
+                Self::Exhausted => "exhausted",
+            }
+        )
+    }
+}
+
+// the stages of getting cold, from fine to dead
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub enum Chill {
+    Comfortable,
+    Cold,
+    Shivering,
+    Numb,
+    Failing,
+    Frozen,
+}
+
+impl Chill {
+    pub fn classify(body: f32) -> Self {
+        if body >= 36.0 {
+            Self::Comfortable
+        } else if body >= 35.0 {
+            Self::Cold
+        } else if body >= 33.0 {
+            Self::Shivering
+        } else if body >= 30.0 {
+            Self::Numb
+        } else if body > Player::FROZEN_BODY {
+            Self::Failing
+        } else {
+            Self::Frozen
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use crate::context::{Season, Sky, Wind};
+
+use std::fmt::{self, Display, Formatter};
+
+// what is falling out of the sky. this is never stored, only worked out from
+// the sky, temperature, wind and season, so the narration, the description
+// and the player's body always agree on it. the temperature is the one where
+// it lands, which is colder the higher you climb, so the snowline needs no
+// rule of its own.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Precipitation {
+    pub kind: Kind,
+    pub intensity: Intensity,
+    pub thunder: bool,
+}
+
+impl Precipitation {
+    pub fn classify(sky: Sky, temp: i8, wind: Wind, season: Season) -> Option<Self> {
+        if sky != Sky::Rain {
+            return None;
+        }
+
+        // the clouds that bring the wind bring the heaviest falls
+        let intensity = match wind {
+            Wind::None | Wind::Light => Intensity::Light,
+            Wind::Medium => Intensity::Moderate,
+            Wind::High => Intensity::Heavy,
+        };
+        // thunderstorms need warm, rising air
+        let thunder = intensity == Intensity::Heavy
+            && temp >= 12
+            && matches!(season, Season::Spring | Season::Summer);
+
+        let kind = match temp {
+            i8::MIN..=-3 => Kind::Snow,
+            // rain falling from a warmer layer freezes as it lands
+            -2..=0 if season == Season::Winter => Kind::FreezingRain,
+            -2..=2 => Kind::Sleet,
+            // the cooler storms let hail reach the ground before it melts
+            12..=17 if thunder => Kind::Hail,
+            _ => Kind::Rain,
+        };
+
+        Some(Self {
+            kind,
+            intensity,
+            thunder,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Kind {
+    Rain,
+    Sleet,
+    FreezingRain,
+    Snow,
+    Hail,
+}
+
+impl Display for Kind {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Rain => "rain",
+                Self::Sleet => "sleet",
+                Self::FreezingRain => "freezing rain",
+                Self::Snow => "snow",
+                Self::Hail => "hail",
+            }
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub enum Intensity {
+    Light,
+    Moderate,
+    Heavy,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::terrain::Terrain;
+
+    fn kind(temp: i8, season: Season) -> Kind {
+        Precipitation::classify(Sky::Rain, temp, Wind::Light, season)
+            .unwrap()
+            .kind
+    }
+
+    #[test]
+    fn only_rain_clouds_bring_anything() {
+        for sky in [Sky::Clear, Sky::Clouds] {
+            assert_eq!(
+                Precipitation::classify(sky, 0, Wind::High, Season::Winter),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn cold_air_gets_snow() {
+        assert_eq!(kind(15, Season::Autumn), Kind::Rain);
+        assert_eq!(kind(-5, Season::Summer), Kind::Snow);
+        assert_eq!(kind(2, Season::Spring), Kind::Sleet);
+        assert_eq!(kind(-1, Season::Winter), Kind::FreezingRain);
+    }
+
+    #[test]
+    fn heights_get_snow_from_the_same_air() {
+        // a spring shower that's rain in the valley
+        let at = |elevation| {
+            let temp = (4.0 - Terrain::lapse(elevation)).round() as i8;
+            kind(temp, Season::Spring)
+        };
+        assert_eq!(at(Terrain::BASE_ELEVATION), Kind::Rain);
+        assert_eq!(at(Terrain::BASE_ELEVATION + 600.0), Kind::Sleet);
+        assert_eq!(at(Terrain::BASE_ELEVATION + 1200.0), Kind::Snow);
+    }
+
+    #[test]
+    fn wind_brings_heavier_falls_and_thunder() {
+        let falling = |wind, temp, season| Precipitation::classify(Sky::Rain, temp, wind, season);
+        assert_eq!(
+            falling(Wind::None, 20, Season::Summer).unwrap().intensity,
+            Intensity::Light
+        );
+        let storm = falling(Wind::High, 20, Season::Summer).unwrap();
+        assert_eq!(storm.intensity, Intensity::Heavy);
+        assert!(storm.thunder);
+        assert_eq!(
+            falling(Wind::High, 14, Season::Summer).unwrap().kind,
+            Kind::Hail
+        );
+        assert!(!falling(Wind::High, 20, Season::Winter).unwrap().thunder);
+    }
+}
@@ -0,0 +1,224 @@
+use crate::context::Sky;
+
+use std::fmt::{self, Display, Formatter};
+
+// the air pressure over the region, at sea level. it wanders up and down over
+// days, pulled back toward the average, and low or falling pressure brings
+// cloud, rain and wind with it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Pressure {
+    hpa: f32,
+    trend: f32, // hPa per hour
+}
+
+impl Pressure {
+    pub const AVERAGE: f32 = 1013.0;
+    pub const LOWEST: f32 = 960.0;
+    pub const HIGHEST: f32 = 1050.0;
+    pub const MAX_TREND: f32 = 1.0;
+    // how hard the trend is pulled back toward the average, per hPa away
+    const PULL: f32 = 0.06;
+    // hPa away from the average, or hPa per hour of change, that doubles or
+    // halves the odds of rain
+    const LOW: f32 = 12.0;
+    const FALLING: f32 = 0.8;
+
+    pub const fn new(hpa: f32, trend: f32) -> Self {
+        Self { hpa, trend }
+    }
+
+    pub const fn hpa(&self) -> f32 {
+        self.hpa
+    }
+
+    pub const fn trend(&self) -> f32 {
+        self.trend
+    }
+
+    pub fn tendency(&self) -> Tendency {
+        Tendency::classify(self.trend)
+    }
+
+    // spend `mins` minutes going the way it's going
+    pub fn pass(&mut self, mins: u64) {
+        let hpa = self.hpa + self.trend * mins as f32 / 60.0;
+        self.hpa = hpa.clamp(Self::LOWEST, Self::HIGHEST);
+    }
+
+    // head off a new way. `roll` is in [0, 1)
+    pub fn turn(&mut self, roll: f32) {
+        let trend = (roll * 2.0 - 1.0) * Self::MAX_TREND + (Self::AVERAGE - self.hpa) * Self::PULL;
+        self.trend = trend.clamp(-Self::MAX_TREND, Self::MAX_TREND);
+    }
+
+    // how unsettled the weather is: 0 as usual, positive when the pressure is
+    // low or falling, negative when it's high or rising
+    pub fn unsettled(&self) -> f32 {
+        (Self::AVERAGE - self.hpa) / Self::LOW - self.trend / Self::FALLING
+    }
+
+    // how many times more often the sky turns this way than it would at
+    // steady, average pressure
+    pub fn sky_factor(&self, sky: Sky) -> f32 {
+        match sky {
+            Sky::Clear => 2.0_f32.powf(-self.unsettled()),
+            Sky::Clouds => 1.0,
+            Sky::Rain => 2.0_f32.powf(self.unsettled()),
+        }
+    }
+}
+
+impl Default for Pressure {
+    fn default() -> Self {
+        Self::new(Self::AVERAGE, 0.0)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Tendency {
+    FallingRapidly,
+    Falling,
+    Steady,
+    Rising,
+    RisingRapidly,
+}
+
+impl Tendency {
+    pub fn classify(trend: f32) -> Self {
+        if trend <= -0.8 {
+            Self::FallingRapidly
+        } else if trend <= -0.2 {
+            Self::Falling
+        } else if trend < 0.2 {
+            Self::Steady
+        } else if trend < 0.8 {
+            Self::Rising
+        } else {
+            Self::RisingRapidly
+        }
+    }
+}
+
+impl Display for Tendency {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::FallingRapidly => "falling rapidly",
+                Self::Falling => "falling slowly",
+                Self::Steady => "steady",
+                Self::Rising => "rising slowly",
+                Self::RisingRapidly => "rising rapidly",
+            }
+        )
+    }
+}
+
+// the words around the face of the barometer
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Dial {
+    Stormy,
+    Rain,
+    Change,
+    Fair,
+    VeryDry,
+}
+
+impl Dial {
+    pub fn classify(hpa: f32) -> Self {
+        if hpa < 985.0 {
+            Self::Stormy
+        } else if hpa < 1002.0 {
+            Self::Rain
+        } else if hpa < 1020.0 {
+            Self::Change
+        } else if hpa < 1035.0 {
+            Self::Fair
+        } else {
+            Self::VeryDry
+        }
+    }
+}
+
+impl Display for Dial {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Stormy => "stormy",
+                Self::Rain => "rain",
+                Self::Change => "change",
+                Self::Fair => "fair",
+                Self::VeryDry => "very dry",
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stays_in_range() {
+        let mut low = Pressure::new(970.0, -Pressure::MAX_TREND);
+        low.pass(24 * 60);
+        assert_eq!(low.hpa(), Pressure::LOWEST);
+
+        let mut high = Pressure::new(1040.0, Pressure::MAX_TREND);
+        high.pass(24 * 60);
+        assert_eq!(high.hpa(), Pressure::HIGHEST);
+    }
+
+    #[test]
+    fn turns_back_toward_the_average() {
+        let mut low = Pressure::new(980.0, 0.0);
+        low.turn(0.5);
+        assert!(low.trend() > 0.0);
+
+        let mut high = Pressure::new(1040.0, 0.0);
+        high.turn(0.5);
+        assert!(high.trend() < 0.0);
+
+        for roll in [0.0, 0.999] {
+            let mut pressure = Pressure::new(Pressure::LOWEST, 0.0);
+            pressure.turn(roll);
+            assert!(pressure.trend().abs() <= Pressure::MAX_TREND);
+        }
+    }
+
+    #[test]
+    fn low_and_falling_brings_rain() {
+        let steady = Pressure::default();
+        assert_eq!(steady.unsettled(), 0.0);
+        assert_eq!(steady.sky_factor(Sky::Rain), 1.0);
+
+        let falling = Pressure::new(995.0, -0.8);
+        assert!(falling.unsettled() > 0.0);
+        assert!(falling.sky_factor(Sky::Rain) > 4.0);
+        assert!(falling.sky_factor(Sky::Clear) < 0.25);
+        assert_eq!(falling.sky_factor(Sky::Clouds), 1.0);
+
+        let rising = Pressure::new(1025.0, 0.5);
+        assert!(rising.sky_factor(Sky::Clear) > rising.sky_factor(Sky::Rain));
+    }
+
+    #[test]
+    fn reads_the_barometer() {
+        assert_eq!(Tendency::classify(-1.0), Tendency::FallingRapidly);
+        assert_eq!(Tendency::classify(-0.3), Tendency::Falling);
+        assert_eq!(Tendency::classify(0.0), Tendency::Steady);
+        assert_eq!(Tendency::classify(0.3), Tendency::Rising);
+        assert_eq!(Tendency::classify(0.8), Tendency::RisingRapidly);
+        assert_eq!(Tendency::FallingRapidly.to_string(), "falling rapidly");
+
+        assert_eq!(Dial::classify(970.0), Dial::Stormy);
+        assert_eq!(Dial::classify(995.0), Dial::Rain);
+        assert_eq!(Dial::classify(Pressure::AVERAGE), Dial::Change);
+        assert_eq!(Dial::classify(1025.0), Dial::Fair);
+        assert_eq!(Dial::classify(1040.0), Dial::VeryDry);
+        assert_eq!(Dial::VeryDry.to_string(), "very dry");
+    }
+}
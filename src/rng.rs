@@ -0,0 +1,108 @@
+use nanorand::crypto::chacha;
+use nanorand::{ChaCha, Rng};
+
+// ChaCha20 keyed by the seed. nanorand's ChaCha keeps its state private, so
+// run the block function here instead. every block only depends on the seed
+// and its position in the stream, so the seed and the number of blocks drawn
+// are enough to pick the stream back up anywhere.
+#[derive(Clone, Debug)]
+pub struct WorldRng {
+    seed: u64,
+    blocks: u64,
+    state: [u32; 16],
+}
+
+impl WorldRng {
+    const ROUNDS: u8 = 20;
+
+    pub fn new(seed: u64) -> Self {
+        Self::resume(seed, 0)
+    }
+
+    pub fn from_entropy() -> Self {
+        Self::new(ChaCha::<20>::new().generate())
+    }
+
+    // continue a stream that has already produced `blocks` blocks
+    pub fn resume(seed: u64, blocks: u64) -> Self {
+        let mut key = [0; 32];
+        key[..8].copy_from_slice(&seed.to_le_bytes());
+
+        Self {
+            seed,
+            blocks,
+            state: chacha::chacha_init(key, [0; 8]),
+        }
+    }
+
+    pub const fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub const fn blocks(&self) -> u64 {
+        self.blocks
+    }
+}
+
+impl Rng<64> for WorldRng {
+    fn rand(&mut self) -> [u8; 64] {
+        // the block counter is the position in the stream
+        self.state[12] = self.blocks as u32;
+        self.state[13] = (self.blocks >> 32) as u32;
+        self.blocks = self.blocks.wrapping_add(1);
+
+        let block = chacha::chacha_block::<{ Self::ROUNDS }>(self.state);
+        let mut bytes = [0; 64];
+        for (chunk, word) in bytes.chunks_exact_mut(4).zip(block) {
+            chunk.copy_from_slice(&word.to_ne_bytes());
+        }
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_stream() {
+        let (mut a, mut b) = (WorldRng::new(42), WorldRng::new(42));
+        for _ in 0..100 {
+            assert_eq!(a.generate::<u64>(), b.generate::<u64>());
+        }
+        assert_ne!(
+            WorldRng::new(1).generate::<u64>(),
+            WorldRng::new(2).generate::<u64>()
+        );
+    }
+
+    #[test]
+    fn matches_nanorand() {
+        let mut key = [0; 32];
+        key[..8].copy_from_slice(&42_u64.to_le_bytes());
+        let mut chacha = ChaCha::<20>::new_key(key, [0; 8]);
+        let mut rng = WorldRng::new(42);
+        for _ in 0..10 {
+            assert_eq!(rng.rand(), chacha.rand());
+        }
+    }
+
+    #[test]
+    fn resume_continues_the_stream() {
+        let mut rng = WorldRng::new(7);
+        for _ in 0..37 {
+            rng.generate::<u32>();
+        }
+        let mut resumed = WorldRng::resume(rng.seed(), rng.blocks());
+        for _ in 0..100 {
+            assert_eq!(rng.generate::<u64>(), resumed.generate::<u64>());
+        }
+    }
+
+    #[test]
+    fn resume_far_along_is_immediate() {
+        let mut rng = WorldRng::resume(7, u64::MAX / 2);
+        rng.generate::<u64>();
+        assert_eq!(rng.blocks(), u64::MAX / 2 + 1);
+    }
+}
@@ -0,0 +1,656 @@
+use crate::action::Direction;
+use crate::context::{Coord, Date, Location, Sky, Time, Wind};
+use crate::front::{Front, Kind};
+use crate::ground::{Ground, Stale};
+use crate::player::Player;
+use crate::pressure::Pressure;
+use crate::rng::WorldRng;
+use crate::schedule::Schedule;
+use crate::weather::Field;
+
+use std::fmt::Display;
+use std::io::{BufRead, Error, ErrorKind, Lines, Write};
+
+const MAGIC: &str = "description-system save";
+
+// bump this whenever the layout below changes. old saves are rejected rather
+// than guessed at.
+pub const VERSION: u32 = 16;
+
+// a value that can be written to and read back from a single save line
+pub trait Persist: Sized {
+    fn store(&self) -> String;
+    fn restore(s: &str) -> Option<Self>;
+}
+
+pub struct SaveWriter<W: Write> {
+    w: W,
+}
+
+impl<W: Write> SaveWriter<W> {
+    pub fn new(mut w: W) -> Result<Self, Error> {
+        writeln!(w, "{} {}", MAGIC, VERSION)?;
+        Ok(Self { w })
+    }
+
+    pub fn field<T: Persist>(&mut self, key: &str, value: &T) -> Result<(), Error> {
+        writeln!(self.w, "{} {}", key, value.store())
+    }
+
+    // multi-line text, stored as a line count followed by the lines themselves
+    pub fn text(&mut self, key: &str, text: &str) -> Result<(), Error> {
+        writeln!(self.w, "{} {}", key, text.lines().count())?;
+        for line in text.lines() {
+            writeln!(self.w, "{}", line)?;
+        }
+        Ok(())
+    }
+
+    // one value per line, after a line with the count
+    pub fn list<'a, T: Persist + 'a>(
+        &mut self,
+        key: &str,
+        values: impl ExactSizeIterator<Item = &'a T>,
+    ) -> Result<(), Error> {
+        writeln!(self.w, "{} {}", key, values.len())?;
+        for value in values {
+            writeln!(self.w, "{}", value.store())?;
+        }
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> Result<(), Error> {
+        self.w.flush()
+    }
+}
+
+pub struct SaveReader<R: BufRead> {
+    lines: Lines<R>,
+    line_no: usize,
+}
+
+impl<R: BufRead> SaveReader<R> {
+    pub fn new(r: R) -> Result<Self, Error> {
+        let mut reader = Self {
+            lines: r.lines(),
+            line_no: 0,
+        };
+
+        let header = reader.next_line()?;
+        let version = header
+            .strip_prefix(MAGIC)
+            .and_then(|rest| rest.trim().parse::<u32>().ok())
+            .ok_or_else(|| reader.error("not a save file"))?;
+        if version != VERSION {
+            return Err(reader.error(format!(
+                "save version {} is not supported (expected {})",
+                version, VERSION
+            )));
+        }
+
+        Ok(reader)
+    }
+
+    pub fn field<T: Persist>(&mut self, key: &str) -> Result<T, Error> {
+        let value = self.value(key)?;
+        T::restore(&value).ok_or_else(|| self.error(format!("bad value for '{}'", key)))
+    }
+
+    pub fn text(&mut self, key: &str) -> Result<String, Error> {
+        let count = self.field::<usize>(key)?;
+        let mut text = String::new();
+        for _ in 0..count {
+            text.push_str(&self.next_line()?);
+            text.push('\n');
+        }
+        Ok(text)
+    }
+
+    pub fn list<T: Persist>(&mut self, key: &str) -> Result<Vec<T>, Error> {
+        // the count can't be trusted to size anything up front
+        let count = self.field::<usize>(key)?;
+        let mut values = Vec::new();
+        for _ in 0..count {
+            let line = self.next_line()?;
+            let value =
+                T::restore(&line).ok_or_else(|| self.error(format!("bad entry in '{}'", key)))?;
+            values.push(value);
+        }
+        Ok(values)
+    }
+
+    fn value(&mut self, key: &str) -> Result<String, Error> {
+        let line = self.next_line()?;
+        match line.split_once(' ') {
+            Some((found, value)) if found == key => Ok(value.to_string()),
+            _ => Err(self.error(format!("expected '{}'", key))),
+        }
+    }
+
+    fn next_line(&mut self) -> Result<String, Error> {
+        self.line_no += 1;
+        match self.lines.next() {
+            Some(line) => line,
+            None => Err(self.error("unexpected end of file")),
+        }
+    }
+
+    fn error(&self, msg: impl Display) -> Error {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!("save line {}: {}", self.line_no, msg),
+        )
+    }
+}
+
+impl Persist for i8 {
+    fn store(&self) -> String {
+        self.to_string()
+    }
+
+    fn restore(s: &str) -> Option<Self> {
+        s.parse().ok()
+    }
+}
+
+impl Persist for f32 {
+    fn store(&self) -> String {
+        self.to_string()
+    }
+
+    fn restore(s: &str) -> Option<Self> {
+        s.parse().ok().filter(|value: &f32| value.is_finite())
+    }
+}
+
+impl Persist for usize {
+    fn store(&self) -> String {
+        self.to_string()
+    }
+
+    fn restore(s: &str) -> Option<Self> {
+        s.parse().ok()
+    }
+}
+
+impl<T: Persist> Persist for Option<T> {
+    fn store(&self) -> String {
+        match self {
+            Some(value) => value.store(),
+            None => "-".to_string(),
+        }
+    }
+
+    fn restore(s: &str) -> Option<Self> {
+        match s {
+            "-" => Some(None),
+            _ => T::restore(s).map(Some),
+        }
+    }
+}
+
+impl Persist for WorldRng {
+    fn store(&self) -> String {
+        format!("{} {}", self.seed(), self.blocks())
+    }
+
+    fn restore(s: &str) -> Option<Self> {
+        let (seed, blocks) = s.split_once(' ')?;
+        Some(Self::resume(seed.parse().ok()?, blocks.parse().ok()?))
+    }
+}
+
+impl Persist for Schedule {
+    fn store(&self) -> String {
+        let (temp, wind, front, pressure, sky) = self.parts();
+        let mut s = format!("{} {} {} {}", temp, wind, front, pressure);
+        for (at, new_sky) in sky {
+            s.push_str(&format!(" {}:{}", at, new_sky.store()));
+        }
+        s
+    }
+
+    fn restore(s: &str) -> Option<Self> {
+        let mut parts = s.split(' ');
+        let temp = parts.next()?.parse().ok()?;
+        let wind = parts.next()?.parse().ok()?;
+        let front = parts.next()?.parse().ok()?;
+        let pressure = parts.next()?.parse().ok()?;
+
+        let mut sky = [(0, Sky::Clear); 3];
+        for slot in sky.iter_mut() {
+            let (at, new_sky) = parts.next()?.split_once(':')?;
+            *slot = (at.parse().ok()?, Sky::restore(new_sky)?);
+        }
+        if parts.next().is_some() {
+            return None;
+        }
+
+        Some(Self::from_parts(temp, wind, front, pressure, sky))
+    }
+}
+
+impl Persist for Coord {
+    fn store(&self) -> String {
+        format!("{} {}", self.n, self.w)
+    }
+
+    fn restore(s: &str) -> Option<Self> {
+        let (n, w) = s.split_once(' ')?;
+        Some(Self {
+            n: n.parse().ok()?,
+            w: w.parse().ok()?,
+        })
+    }
+}
+
+impl Persist for Location {
+    fn store(&self) -> String {
+        let (kind, coord) = match self {
+            Self::Forest(coord) => ("forest", coord),
+            Self::Meadow(coord) => ("meadow", coord),
+            Self::LakeShore(coord) => ("lake-shore", coord),
+            Self::Ridge(coord) => ("ridge", coord),
+            Self::Bog(coord) => ("bog", coord),
+            Self::RiverValley(coord) => ("river-valley", coord),
+        };
+        format!("{} {}", kind, coord.store())
+    }
+
+    fn restore(s: &str) -> Option<Self> {
+        let (kind, coord) = s.split_once(' ')?;
+        let coord = Coord::restore(coord)?;
+        match kind {
+            "forest" => Some(Self::Forest(coord)),
+            "meadow" => Some(Self::Meadow(coord)),
+            "lake-shore" => Some(Self::LakeShore(coord)),
+            "ridge" => Some(Self::Ridge(coord)),
+            "bog" => Some(Self::Bog(coord)),
+            "river-valley" => Some(Self::RiverValley(coord)),
+            _ => None,
+        }
+    }
+}
+
+impl Persist for Time {
+    fn store(&self) -> String {
+        let (hour, min) = self.get();
+        format!("{:0>2}:{:0>2}", hour, min)
+    }
+
+    fn restore(s: &str) -> Option<Self> {
+        let (hour, min) = s.split_once(':')?;
+        let (hour, min) = (hour.parse().ok()?, min.parse().ok()?);
+        if u16::from(hour) < Self::DAY_HOURS && u16::from(min) < Self::HOUR_MINS {
+            Some(Self::new(hour, min))
+        } else {
+            None
+        }
+    }
+}
+
+impl Persist for Date {
+    fn store(&self) -> String {
+        let (year, day) = self.get();
+        format!("{} {}", year, day)
+    }
+
+    fn restore(s: &str) -> Option<Self> {
+        let (year, day) = s.split_once(' ')?;
+        let (year, day) = (year.parse().ok()?, day.parse().ok()?);
+        if day < Self::YEAR_DAYS {
+            Some(Self::new(year, day))
+        } else {
+            None
+        }
+    }
+}
+
+impl Persist for Sky {
+    fn store(&self) -> String {
+        match self {
+            Self::Clear => "clear",
+            Self::Clouds => "clouds",
+            Self::Rain => "rain",
+        }
+        .to_string()
+    }
+
+    fn restore(s: &str) -> Option<Self> {
+        match s {
+            "clear" => Some(Self::Clear),
+            "clouds" => Some(Self::Clouds),
+            "rain" => Some(Self::Rain),
+            _ => None,
+        }
+    }
+}
+
+impl Persist for Wind {
+    fn store(&self) -> String {
+        match self {
+            Self::None => "none",
+            Self::Light => "light",
+            Self::Medium => "medium",
+            Self::High => "high",
+        }
+        .to_string()
+    }
+
+    fn restore(s: &str) -> Option<Self> {
+        match s {
+            "none" => Some(Self::None),
+            "light" => Some(Self::Light),
+            "medium" => Some(Self::Medium),
+            "high" => Some(Self::High),
+            _ => None,
+        }
+    }
+}
+
+impl Persist for Direction {
+    fn store(&self) -> String {
+        self.to_string()
+    }
+
+    fn restore(s: &str) -> Option<Self> {
+        match s {
+            "north" => Some(Self::North),
+            "south" => Some(Self::South),
+            "east" => Some(Self::East),
+            "west" => Some(Self::West),
+            _ => None,
+        }
+    }
+}
+
+// nobody saves in their sleep
+impl Persist for Player {
+    fn store(&self) -> String {
+        format!(
+            "{} {} {} {}",
+            self.fatigue().store(),
+            self.body().store(),
+            self.wetness().store(),
+            if self.is_mortal() { "mortal" } else { "hardy" }
+        )
+    }
+
+    fn restore(s: &str) -> Option<Self> {
+        let parts: Vec<&str> = s.split(' ').collect();
+        let [fatigue, body, wetness, mortal] = parts[..] else {
+            return None;
+        };
+        let unit = |s| f32::restore(s).filter(|value| (0.0..=1.0).contains(value));
+        let body = f32::restore(body)
+            .filter(|body| (Player::FROZEN_BODY..=Player::NORMAL_BODY).contains(body))?;
+        let (fatigue, wetness) = (unit(fatigue)?, unit(wetness)?);
+        let (mortal, body) = match mortal {
+            "mortal" => (true, body),
+            // the cold never takes a hardy player below this
+            "hardy" => (false, body.max(Player::HARDY_BODY)),
+            _ => return None,
+        };
+
+        Some(Self::from_parts(fatigue, body, wetness, mortal))
+    }
+}
+
+// the ground at one coord: "n w snow ice mud leaves", then the weather it
+// was left in if it's out of range (see Stale)
+impl Persist for (Coord, Ground, Option<Stale>) {
+    fn store(&self) -> String {
+        let (coord, ground, stale) = self;
+        let (snow, ice, mud, leaves) = ground.parts();
+        format!(
+            "{} {} {} {} {} {}",
+            coord.store(),
+            snow.store(),
+            ice.store(),
+            mud.store(),
+            leaves.store(),
+            stale.store()
+        )
+    }
+
+    fn restore(s: &str) -> Option<Self> {
+        let parts: Vec<&str> = s.splitn(7, ' ').collect();
+        let [n, w, snow, ice, mud, leaves, stale] = parts[..] else {
+            return None;
+        };
+        let coord = Coord::restore(&format!("{} {}", n, w))?;
+        let amount = |s| f32::restore(s).filter(|value| *value >= 0.0);
+        let ground = Ground::from_parts(amount(snow)?, amount(ice)?, amount(mud)?, amount(leaves)?);
+        Some((coord, ground, Option::<Stale>::restore(stale)?))
+    }
+}
+
+// "since sky temp wind"
+impl Persist for Stale {
+    fn store(&self) -> String {
+        format!(
+            "{} {} {} {}",
+            self.since,
+            self.sky.store(),
+            self.temp.store(),
+            self.wind.store()
+        )
+    }
+
+    fn restore(s: &str) -> Option<Self> {
+        let parts: Vec<&str> = s.split(' ').collect();
+        let [since, sky, temp, wind] = parts[..] else {
+            return None;
+        };
+        Some(Self {
+            since: since.parse().ok()?,
+            sky: Sky::restore(sky)?,
+            temp: i8::restore(temp)?,
+            wind: Wind::restore(wind)?,
+        })
+    }
+}
+
+// "storm|clear n w radius until"
+impl Persist for Front {
+    fn store(&self) -> String {
+        let (kind, n, w, radius, until) = self.parts();
+        let kind = match kind {
+            Kind::Storm => "storm",
+            Kind::Clear => "clear",
+        };
+        format!(
+            "{} {} {} {} {}",
+            kind,
+            n.store(),
+            w.store(),
+            radius.store(),
+            until
+        )
+    }
+
+    fn restore(s: &str) -> Option<Self> {
+        let parts: Vec<&str> = s.split(' ').collect();
+        let [kind, n, w, radius, until] = parts[..] else {
+            return None;
+        };
+        let kind = match kind {
+            "storm" => Kind::Storm,
+            "clear" => Kind::Clear,
+            _ => return None,
+        };
+        let radius = f32::restore(radius).filter(|radius| *radius > 0.0)?;
+        Some(Self::new(
+            kind,
+            f32::restore(n)?,
+            f32::restore(w)?,
+            radius,
+            until.parse().ok()?,
+        ))
+    }
+}
+
+// "seed n w", the seed already mixed
+impl Persist for Field {
+    fn store(&self) -> String {
+        let (seed, n, w) = self.parts();
+        format!("{} {} {}", seed, n.store(), w.store())
+    }
+
+    fn restore(s: &str) -> Option<Self> {
+        let parts: Vec<&str> = s.split(' ').collect();
+        let [seed, n, w] = parts[..] else {
+            return None;
+        };
+        Some(Self::from_parts(
+            seed.parse().ok()?,
+            f32::restore(n)?,
+            f32::restore(w)?,
+        ))
+    }
+}
+
+// "hpa trend"
+impl Persist for Pressure {
+    fn store(&self) -> String {
+        format!("{} {}", self.hpa().store(), self.trend().store())
+    }
+
+    fn restore(s: &str) -> Option<Self> {
+        let (hpa, trend) = s.split_once(' ')?;
+        let hpa = f32::restore(hpa).filter(|hpa| (Self::LOWEST..=Self::HIGHEST).contains(hpa))?;
+        let trend = f32::restore(trend).filter(|trend| trend.abs() <= Self::MAX_TREND)?;
+        Some(Self::new(hpa, trend))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use nanorand::Rng;
+    use std::fmt::Debug;
+    use std::io::Cursor;
+
+    fn round_trip<T: Persist + PartialEq + Debug>(value: T) {
+        assert_eq!(T::restore(&value.store()), Some(value));
+    }
+
+    fn reader(body: &str) -> SaveReader<Cursor<String>> {
+        SaveReader::new(Cursor::new(format!("{} {}\n{}", MAGIC, VERSION, body))).unwrap()
+    }
+
+    #[test]
+    fn values_round_trip() {
+        round_trip(Coord { n: -3, w: 120 });
+        round_trip(Location::RiverValley(Coord { n: 1, w: -1 }));
+        round_trip(Time::new(23, 59));
+        round_trip(Date::new(3, 364));
+        round_trip(Sky::Rain);
+        round_trip(Wind::Medium);
+        round_trip(Direction::East);
+        round_trip(Some(Direction::North));
+        round_trip(None::<Direction>);
+        round_trip(1.25_f32);
+        round_trip((Coord::new(), Ground::from_parts(1.0, 0.5, 0.0, 2.0), None));
+        round_trip((
+            Coord { n: 4, w: -9 },
+            Ground::default(),
+            Some(Stale {
+                since: 1_000,
+                sky: Sky::Rain,
+                temp: -4,
+                wind: Wind::High,
+            }),
+        ));
+        round_trip(Front::new(Kind::Storm, 10.5, -3.0, 12.0, 9_000));
+        round_trip(Field::from_parts(77, 1.5, -2.5));
+        round_trip(Pressure::new(998.5, -0.4));
+        round_trip(Schedule::from_parts(
+            1,
+            2,
+            3,
+            4,
+            [(5, Sky::Clear), (6, Sky::Clouds), (7, Sky::Rain)],
+        ));
+    }
+
+    #[test]
+    fn out_of_range_values_are_rejected() {
+        assert_eq!(Time::restore("24:00"), None);
+        assert_eq!(Date::restore("1 365"), None);
+        assert_eq!(f32::restore("NaN"), None);
+        assert_eq!(f32::restore("inf"), None);
+        assert_eq!(Front::restore("storm 0 0 -1 10"), None);
+        assert_eq!(Pressure::restore("NaN 0"), None);
+        assert_eq!(Pressure::restore("1013 NaN"), None);
+        assert_eq!(Pressure::restore("20000 0"), None);
+        assert_eq!(Pressure::restore("1013 -5"), None);
+        assert!(Player::restore("0 20 0 hardy").is_none());
+        assert!(Player::restore("0 40 0 mortal").is_none());
+        assert_eq!(
+            Player::restore("0 29 0 hardy").map(|player| player.body()),
+            Some(Player::HARDY_BODY)
+        );
+        assert_eq!(
+            <(Coord, Ground, Option<Stale>)>::restore("0 0 -1 0 0 0 -"),
+            None
+        );
+        assert_eq!(
+            <(Coord, Ground, Option<Stale>)>::restore("0 0 0 0 0 0"),
+            None
+        );
+    }
+
+    #[test]
+    fn rng_resumes_where_it_left_off() {
+        let mut rng = WorldRng::new(9);
+        for _ in 0..5 {
+            rng.generate::<u64>();
+        }
+        let mut restored = WorldRng::restore(&rng.store()).unwrap();
+        assert_eq!(restored.generate::<u64>(), rng.generate::<u64>());
+    }
+
+    #[test]
+    fn writes_what_it_reads() {
+        let mut out = Vec::new();
+        let mut save = SaveWriter::new(&mut out).unwrap();
+        save.field("sky", &Sky::Clouds).unwrap();
+        save.list("dirs", [Direction::North, Direction::West].iter())
+            .unwrap();
+        save.text("desc", "one\ntwo\n").unwrap();
+        save.finish().unwrap();
+
+        let mut save = SaveReader::new(out.as_slice()).unwrap();
+        assert_eq!(save.field::<Sky>("sky").unwrap(), Sky::Clouds);
+        assert_eq!(
+            save.list::<Direction>("dirs").unwrap(),
+            [Direction::North, Direction::West]
+        );
+        assert_eq!(save.text("desc").unwrap(), "one\ntwo\n");
+    }
+
+    #[test]
+    fn rejects_other_versions() {
+        let save = format!("{} {}\n", MAGIC, VERSION - 1);
+        assert!(SaveReader::new(save.as_bytes()).is_err());
+        assert!(SaveReader::new("hello\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn huge_counts_are_an_error() {
+        let body = format!("dirs {}\nnorth\n", usize::MAX);
+        assert!(reader(&body).list::<Direction>("dirs").is_err());
+        let body = format!("desc {}\none\n", usize::MAX);
+        assert!(reader(&body).text("desc").is_err());
+    }
+
+    #[test]
+    fn errors_name_the_line() {
+        let err = reader("sky cloudy\n").field::<Sky>("sky").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert!(err.to_string().contains("line 2"));
+        assert!(reader("wind none\n").field::<Sky>("sky").is_err());
+    }
+}
@@ -0,0 +1,191 @@
+use crate::context::{Location, Sky};
+use crate::pressure::Pressure;
+use crate::rng::WorldRng;
+
+use nanorand::Rng;
+
+// the location chances are rolled out of this many, once per minute
+const CHANCE_OUT_OF: f64 = 100_001.0;
+// weather systems blow in from elsewhere, whatever the location
+const CHANCE_FRONT: u32 = 208; // 1 new front / 8 hrs
+const CHANCE_PRESSURE_TURN: u32 = 278; // 1 change / 6 hrs
+
+// a weather process that changes state at random
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Process {
+    Temp,
+    Wind,
+    Sky(Sky),
+    Front,    // a new front forms upwind
+    Pressure, // the pressure starts rising or falling differently
+}
+
+// instead of rolling every process once per simulated minute, remember when
+// each one next fires. waiting times are drawn so the odds of firing on any
+// given minute are the same as the old per-minute rolls, but skipping ahead a
+// month only costs as much as the changes that actually happen.
+//
+// times are minutes since the start of the world (see Context::clock).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Schedule {
+    temp: u64,
+    wind: u64,
+    front: u64,
+    pressure: u64,
+    sky: [(u64, Sky); 3],
+}
+
+impl Schedule {
+    pub fn new(rng: &mut WorldRng, loc: &Location, pressure: Pressure, now: u64) -> Self {
+        let mut schedule = Self {
+            temp: now + wait(rng, loc.chance_temp_change()),
+            wind: now + wait(rng, loc.chance_wind_change()),
+            front: now + wait(rng, CHANCE_FRONT),
+            pressure: now + wait(rng, CHANCE_PRESSURE_TURN),
+            sky: [(0, Sky::Clear); 3],
+        };
+        schedule.redraw_sky(rng, loc, pressure, now);
+        schedule
+    }
+
+    // used when restoring a save
+    pub const fn from_parts(
+        temp: u64,
+        wind: u64,
+        front: u64,
+        pressure: u64,
+        sky: [(u64, Sky); 3],
+    ) -> Self {
+        Self {
+            temp,
+            wind,
+            front,
+            pressure,
+            sky,
+        }
+    }
+
+    pub const fn parts(&self) -> (u64, u64, u64, u64, [(u64, Sky); 3]) {
+        (self.temp, self.wind, self.front, self.pressure, self.sky)
+    }
+
+    // the sky chances follow the pressure, so draw them again when it turns
+    pub fn redraw_sky(&mut self, rng: &mut WorldRng, loc: &Location, pressure: Pressure, now: u64) {
+        for (slot, (chance, new_sky)) in self.sky.iter_mut().zip(loc.chances_sky(pressure)) {
+            *slot = (now + wait(rng, chance), new_sky);
+        }
+    }
+
+    // the process that fires soonest, and when
+    pub fn next(&self) -> (u64, Process) {
+        let mut next = (self.temp, Process::Temp);
+        if self.wind < next.0 {
+            next = (self.wind, Process::Wind);
+        }
+        if self.front < next.0 {
+            next = (self.front, Process::Front);
+        }
+        if self.pressure < next.0 {
+            next = (self.pressure, Process::Pressure);
+        }
+        for (at, sky) in self.sky {
+            if at < next.0 {
+                next = (at, Process::Sky(sky));
+            }
+        }
+        next
+    }
+
+    // draw the next firing time for a process that just fired at `now`
+    pub fn reschedule(
+        &mut self,
+        rng: &mut WorldRng,
+        loc: &Location,
+        pressure: Pressure,
+        process: Process,
+        now: u64,
+    ) {
+        match process {
+            Process::Temp => self.temp = now + wait(rng, loc.chance_temp_change()),
+            Process::Wind => self.wind = now + wait(rng, loc.chance_wind_change()),
+            Process::Front => self.front = now + wait(rng, CHANCE_FRONT),
+            Process::Pressure => self.pressure = now + wait(rng, CHANCE_PRESSURE_TURN),
+            Process::Sky(sky) => {
+                let chances = loc.chances_sky(pressure);
+                for ((at, slot_sky), (chance, _)) in self.sky.iter_mut().zip(chances) {
+                    if *slot_sky == sky {
+                        *at = now + wait(rng, chance);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// minutes until a roll with this per-minute chance first succeeds. the
+// processes are memoryless, so this can be redrawn at any time (for example
+// when the chances change) without changing the odds.
+fn wait(rng: &mut WorldRng, chance: u32) -> u64 {
+    let p = f64::from(chance) / CHANCE_OUT_OF;
+    if p <= 0.0 {
+        return u64::MAX / 2;
+    } else if p >= 1.0 {
+        return 1;
+    }
+
+    let u: f64 = rng.generate();
+    ((1.0 - u).ln() / (1.0 - p).ln()).floor() as u64 + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::context::Coord;
+
+    #[test]
+    fn waits_match_the_per_minute_odds() {
+        let mut rng = WorldRng::new(1);
+        for chance in [1_667, 16_667, 50_000] {
+            let p = f64::from(chance) / CHANCE_OUT_OF;
+            let draws = 20_000;
+            let mean =
+                (0..draws).map(|_| wait(&mut rng, chance)).sum::<u64>() as f64 / f64::from(draws);
+            // a geometric distribution has a mean of 1 / p
+            assert!((mean * p - 1.0).abs() < 0.05, "{} gave {}", chance, mean);
+        }
+    }
+
+    #[test]
+    fn waits_are_at_least_a_minute() {
+        let mut rng = WorldRng::new(1);
+        assert!((0..1_000).all(|_| wait(&mut rng, 99_000) >= 1));
+        assert_eq!(wait(&mut rng, 200_000), 1);
+        assert!(wait(&mut rng, 0) > 1_000_000_000);
+    }
+
+    #[test]
+    fn next_is_the_soonest() {
+        let schedule = Schedule::from_parts(
+            50,
+            40,
+            60,
+            70,
+            [(30, Sky::Clear), (20, Sky::Clouds), (80, Sky::Rain)],
+        );
+        assert_eq!(schedule.next(), (20, Process::Sky(Sky::Clouds)));
+    }
+
+    #[test]
+    fn reschedule_only_moves_what_fired() {
+        let mut rng = WorldRng::new(1);
+        let loc = Location::Forest(Coord::new());
+        let mut schedule = Schedule::new(&mut rng, &loc, Pressure::default(), 0);
+        let (_, wind, front, pressure, sky) = schedule.parts();
+
+        schedule.reschedule(&mut rng, &loc, Pressure::default(), Process::Temp, 1_000);
+        let (temp, ..) = schedule.parts();
+        assert!(temp > 1_000);
+        assert_eq!(schedule.parts(), (temp, wind, front, pressure, sky),);
+    }
+}
@@ -0,0 +1,118 @@
+use crate::context::{Date, Time};
+
+// roughly northern scandinavia, which matches the old fixed season tables
+pub const DEFAULT_LATITUDE: f32 = 60.0;
+
+const AXIAL_TILT: f32 = 23.44; // degrees
+
+// where the sun is over the course of one day. clock time is taken to be
+// solar time, so noon is always at 12:00.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Daylight {
+    latitude: f32,    // radians
+    declination: f32, // radians
+    sunrise: Time,
+    sunset: Time,
+}
+
+impl Daylight {
+    pub fn new(date: Date, latitude: f32) -> Self {
+        let (_, day) = date.get();
+        let latitude = latitude.clamp(-90.0, 90.0).to_radians();
+
+        // the solstice falls about ten days before the new year
+        let year_angle = (f32::from(day) + 10.0) / f32::from(Date::YEAR_DAYS) * 360.0;
+        let declination = (-AXIAL_TILT * year_angle.to_radians().cos()).to_radians();
+
+        // hour angle of sunrise, in degrees either side of noon
+        let cos_rise = -latitude.tan() * declination.tan();
+        let half_day = if cos_rise <= -1.0 {
+            180.0 // midnight sun
+        } else if cos_rise >= 1.0 {
+            0.0 // polar night
+        } else {
+            cos_rise.acos().to_degrees()
+        };
+
+        const NOON: f32 = Time::DAY_MINS as f32 / 2.0;
+        let half_day_mins = half_day * 4.0; // the sun moves a degree every 4 minutes
+        let last_min = f32::from(Time::DAY_MINS - 1);
+
+        Self {
+            latitude,
+            declination,
+            sunrise: Time::from_mins((NOON - half_day_mins).clamp(0.0, last_min) as u16),
+            sunset: Time::from_mins((NOON + half_day_mins).clamp(0.0, last_min) as u16),
+        }
+    }
+
+    pub const fn sunrise(&self) -> Time {
+        self.sunrise
+    }
+
+    pub const fn sunset(&self) -> Time {
+        self.sunset
+    }
+
+    pub fn is_up(&self, time: Time) -> bool {
+        self.sunrise.mins() < self.sunset.mins()
+            && time.mins() >= self.sunrise.mins()
+            && time.mins() <= self.sunset.mins()
+    }
+
+    // sine of the sun's elevation above the horizon
+    fn elevation_sin(&self, time: Time) -> f32 {
+        let hour_angle =
+            ((f32::from(time.mins()) - f32::from(Time::DAY_MINS) / 2.0) / 4.0).to_radians();
+        self.latitude.sin() * self.declination.sin()
+            + self.latitude.cos() * self.declination.cos() * hour_angle.cos()
+    }
+
+    // degrees above the horizon
+    pub fn elevation(&self, time: Time) -> f32 {
+        self.elevation_sin(time)
+            .clamp(-1.0, 1.0)
+            .asin()
+            .to_degrees()
+    }
+
+    // 0 at night, rising to 1 at noon
+    pub fn level(&self, time: Time) -> f32 {
+        let noon = self.elevation_sin(Time::new(12, 0));
+        if noon <= 0.0 {
+            0.0
+        } else {
+            (self.elevation_sin(time) / noon).clamp(0.0, 1.0)
+        }
+    }
+
+    // level scaled by intensity: what actually warms the ground
+    pub fn strength(&self, time: Time) -> f32 {
+        self.level(time) * self.intensity()
+    }
+
+    // strength averaged over the whole day and night
+    pub fn mean_strength(&self) -> f32 {
+        let hours = Time::DAY_HOURS as u8;
+        (0..hours)
+            .map(|hour| self.strength(Time::new(hour, 30)))
+            .sum::<f32>()
+            / f32::from(hours)
+    }
+
+    // how strong the noon sun is, from 0.7 in deep winter to 1.0 in high summer
+    pub fn intensity(&self) -> f32 {
+        let noon = |declination: f32| {
+            (self.latitude.sin() * declination.sin() + self.latitude.cos() * declination.cos())
+                .max(0.0)
+        };
+        let tilt = AXIAL_TILT.to_radians().copysign(self.latitude);
+        let (lowest, highest) = (noon(-tilt), noon(tilt));
+
+        if highest - lowest <= f32::EPSILON {
+            1.0
+        } else {
+            0.7 + 0.3 * ((noon(self.declination) - lowest) / (highest - lowest)).clamp(0.0, 1.0)
+        }
+    }
+}
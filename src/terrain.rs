@@ -0,0 +1,131 @@
+use crate::action::Direction;
+use crate::context::Coord;
+use crate::noise;
+
+use std::fmt::{self, Display, Formatter};
+
+// a heightmap over the coord grid. nothing is stored: every sample comes
+// straight from the seed, so the same seed always gives the same hills.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Terrain {
+    seed: u64,
+}
+
+impl Terrain {
+    // metres between neighbouring coords
+    pub const CELL_SIZE: f32 = 50.0;
+    pub const MIN_ELEVATION: f32 = 100.0;
+    pub const MAX_ELEVATION: f32 = 1000.0;
+    // metres per step before climbing or descending is worth mentioning
+    pub const NOTICEABLE_RISE: f32 = 2.0;
+    // the height the weather's own temperature is given at
+    pub const BASE_ELEVATION: f32 = 400.0;
+    // above this nothing breaks the wind
+    pub const EXPOSED_ELEVATION: f32 = 700.0;
+    // the air cools by this much for every metre climbed, celcius
+    const LAPSE_RATE: f32 = 0.0065;
+
+    const SCALE: f32 = 40.0; // coords across the largest hills
+    const OCTAVES: u32 = 3;
+
+    pub const fn new(seed: u64) -> Self {
+        Self {
+            // keep the terrain independent of the weather stream
+            seed: seed ^ 0x7465_7272_6169_6e00,
+        }
+    }
+
+    // metres above sea level
+    pub fn elevation(&self, coord: Coord) -> f32 {
+        let height = noise::fractal(
+            self.seed,
+            f32::from(coord.w),
+            f32::from(coord.n),
+            Self::SCALE,
+            Self::OCTAVES,
+        );
+        Self::MIN_ELEVATION + height * (Self::MAX_ELEVATION - Self::MIN_ELEVATION)
+    }
+
+    // rise in metres per coord toward the north and toward the west
+    pub fn gradient(&self, coord: Coord) -> Gradient {
+        let at = |n: i8, w: i8| self.elevation(Coord { n, w });
+        let (n, w) = (coord.n, coord.w);
+
+        Gradient {
+            n: (at(n.saturating_add(1), w) - at(n.saturating_sub(1), w)) / 2.0,
+            w: (at(n, w.saturating_add(1)) - at(n, w.saturating_sub(1))) / 2.0,
+        }
+    }
+
+    pub fn slope(&self, coord: Coord) -> Slope {
+        Slope::classify(self.gradient(coord).steepness())
+    }
+
+    // how much colder it is at this height than at the base elevation
+    pub fn lapse(elevation: f32) -> f32 {
+        (elevation - Self::BASE_ELEVATION) * Self::LAPSE_RATE
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Gradient {
+    pub n: f32,
+    pub w: f32,
+}
+
+impl Gradient {
+    // rise per metre travelled, along the steepest direction
+    pub fn steepness(&self) -> f32 {
+        self.n.hypot(self.w) / Terrain::CELL_SIZE
+    }
+
+    // metres gained by one step in this direction
+    pub fn rise_toward(&self, direction: Direction) -> f32 {
+        let step = direction.as_coord_with_magnitude(1);
+        self.n * f32::from(step.n) + self.w * f32::from(step.w)
+    }
+
+    // the compass direction that climbs the most
+    pub fn uphill(&self) -> Direction {
+        if self.n.abs() >= self.w.abs() {
+            if self.n >= 0.0 {
+                Direction::North
+            } else {
+                Direction::South
+            }
+        } else if self.w >= 0.0 {
+            Direction::West
+        } else {
+            Direction::East
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub enum Slope {
+    Flat,
+    Gentle,
+    Steep,
+}
+
+impl Slope {
+    pub fn classify(steepness: f32) -> Self {
+        match steepness {
+            s if s < 0.1 => Self::Flat,
+            s if s < 0.3 => Self::Gentle,
+            _ => Self::Steep,
+        }
+    }
+}
+
+impl Display for Slope {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        let adjective = match self {
+            Self::Flat => "flat",
+            Self::Gentle => "gently sloped",
+            Self::Steep => "steeply sloped",
+        };
+        write!(f, "{}", adjective)
+    }
+}
@@ -0,0 +1,112 @@
+use crate::action::Direction;
+use crate::context::{Coord, Sky, Wind};
+use crate::front::{Cover, Front};
+use crate::noise;
+use crate::terrain::{Gradient, Slope};
+
+// the schedule drives one air mass over the whole region, but what it does at
+// any one place depends on the fronts overhead, patches of cloud drifting
+// along with the wind, and the lie of the land. only the drift is stored;
+// each place is worked out when it's looked at, like the world itself.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Field {
+    seed: u64,
+    n: f32, // how far the cloud pattern has been carried, in coords
+    w: f32,
+}
+
+impl Field {
+    const SCALE: f32 = 16.0; // coords across a patch of cloud
+    const BREAKS: f32 = 0.33; // below this, grey skies break up
+    const BUILDS: f32 = 0.69; // above this, clouds build in a clear sky
+
+    // showers only fall from the thicker patches, and fewer of them make it
+    // over a hill into its lee
+    const SHOWERS: f32 = 0.38;
+    const LEE_SHOWERS: f32 = 0.5;
+    const WINDWARD_SHOWERS: f32 = 0.28;
+
+    pub const fn new(seed: u64) -> Self {
+        Self {
+            // keep the clouds independent of the world and the weather stream
+            seed: seed ^ 0x636c_6f75_6400_0000,
+            n: 0.0,
+            w: 0.0,
+        }
+    }
+
+    // used when restoring a save
+    pub const fn from_parts(seed: u64, n: f32, w: f32) -> Self {
+        Self { seed, n, w }
+    }
+
+    pub const fn parts(&self) -> (u64, f32, f32) {
+        (self.seed, self.n, self.w)
+    }
+
+    // the clouds ride along with the fronts
+    pub fn drift(&mut self, mins: u64, wind: Wind, from: Direction) {
+        let distance = wind.speed() * Front::DRIFT * mins as f32 / 60.0;
+        let step = from.opposite().as_coord_with_magnitude(1);
+        self.n += f32::from(step.n) * distance;
+        self.w += f32::from(step.w) * distance;
+    }
+
+    // how thick the cloud is over this coord compared to around it, in [0, 1)
+    pub fn thickness(&self, coord: Coord) -> f32 {
+        let (x, y) = (f32::from(coord.w) - self.w, f32::from(coord.n) - self.n);
+        noise::fractal(self.seed, x, y, Self::SCALE, 2)
+    }
+
+    // the sky over one coord, given the sky over the region
+    pub fn sky(&self, coord: Coord, sky: Sky, cover: Option<Cover>, shelter: Shelter) -> Sky {
+        let thickness = self.thickness(coord);
+        let showers = match shelter {
+            Shelter::Lee => Self::LEE_SHOWERS,
+            Shelter::Open => Self::SHOWERS,
+            Shelter::Windward => Self::WINDWARD_SHOWERS,
+        };
+        let sky = match sky {
+            Sky::Clear if thickness > Self::BUILDS => Sky::Clouds,
+            Sky::Clouds if thickness < Self::BREAKS => Sky::Clear,
+            Sky::Rain if thickness < showers => Sky::Clouds,
+            sky => sky,
+        };
+        cover.map_or(sky, |cover| cover.settle(sky))
+    }
+}
+
+// how a slope sits against the wind
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Shelter {
+    Lee,
+    Open,
+    Windward,
+}
+
+impl Shelter {
+    // a slope rising toward the wind keeps it off, while one falling away
+    // toward it catches the full force
+    pub fn classify(gradient: Gradient, wind: Wind, from: Direction) -> Self {
+        if wind == Wind::None {
+            return Self::Open;
+        }
+        match Slope::classify(gradient.steepness()) {
+            Slope::Flat => Self::Open,
+            _ if gradient.uphill() == from => Self::Lee,
+            Slope::Steep if gradient.uphill() == from.opposite() => Self::Windward,
+            _ => Self::Open,
+        }
+    }
+
+    // the wind as it is felt on this slope
+    pub fn wind(&self, wind: Wind) -> Wind {
+        let mut wind = wind;
+        match self {
+            Self::Lee => wind.decrease(),
+            Self::Open => false,
+            Self::Windward => wind.increase(),
+        };
+        wind
+    }
+}
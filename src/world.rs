@@ -0,0 +1,126 @@
+use crate::context::{Coord, Location};
+use crate::noise;
+use crate::terrain::{Slope, Terrain};
+
+use std::fmt::{self, Display, Formatter};
+
+// decides what is where. like the terrain, everything is derived from the seed
+// on demand, so the world never needs to be stored.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct World {
+    seed: u64,
+    terrain: Terrain,
+}
+
+impl World {
+    const MOISTURE_SCALE: f32 = 30.0;
+    const WARP_SCALE: f32 = 12.0;
+    const WARP: f32 = 4.0; // coords that region borders can wander by
+    const FEATURE_CHANCE: f32 = 0.15;
+
+    pub const fn new(seed: u64) -> Self {
+        Self {
+            // keep the world independent of the weather stream
+            seed: seed ^ 0x776f_726c_6400_0000,
+            terrain: Terrain::new(seed),
+        }
+    }
+
+    pub const fn terrain(&self) -> &Terrain {
+        &self.terrain
+    }
+
+    // how wet the ground is, in [0, 1)
+    pub fn moisture(&self, coord: Coord) -> f32 {
+        let (x, y) = self.warp(coord);
+        noise::fractal(self.seed, x, y, Self::MOISTURE_SCALE, 2)
+    }
+
+    pub fn location(&self, coord: Coord) -> Location {
+        let elevation = self.terrain.elevation(coord);
+        let slope = self.terrain.slope(coord);
+        let moisture = self.moisture(coord);
+
+        if elevation > 680.0 {
+            Location::Ridge(coord)
+        } else if elevation < 380.0 {
+            // low ground collects the water
+            match slope {
+                Slope::Flat if moisture > 0.5 => Location::LakeShore(coord),
+                Slope::Flat => Location::Bog(coord),
+                Slope::Gentle | Slope::Steep => Location::RiverValley(coord),
+            }
+        } else if moisture < 0.45 && slope != Slope::Steep {
+            Location::Meadow(coord)
+        } else if moisture > 0.62 && slope == Slope::Flat {
+            Location::Bog(coord)
+        } else {
+            Location::Forest(coord)
+        }
+    }
+
+    // something notable that sits at this coord, if anything
+    pub fn feature(&self, coord: Coord) -> Option<Feature> {
+        let seed = self.seed.wrapping_add(1);
+        let (x, y) = (i32::from(coord.w), i32::from(coord.n));
+        if noise::unit(seed, x, y) >= Self::FEATURE_CHANCE {
+            return None;
+        }
+
+        let choices: &[Feature] = match self.location(coord) {
+            Location::Forest(_) => &[Feature::FallenTree, Feature::Clearing, Feature::Boulder],
+            Location::Meadow(_) => &[Feature::LoneTree, Feature::Boulder, Feature::Stream],
+            Location::LakeShore(_) => &[Feature::Reeds, Feature::Jetty],
+            Location::Ridge(_) => &[Feature::Cairn, Feature::Boulder],
+            Location::Bog(_) => &[Feature::Reeds, Feature::DeadTree],
+            Location::RiverValley(_) => &[Feature::Stream, Feature::Footbridge],
+        };
+        let pick = noise::hash(seed.wrapping_add(1), x, y) % choices.len() as u64;
+        Some(choices[pick as usize])
+    }
+
+    // shift the lookup position a little so region borders aren't straight
+    fn warp(&self, coord: Coord) -> (f32, f32) {
+        let (x, y) = (f32::from(coord.w), f32::from(coord.n));
+        let seed = self.seed.wrapping_add(2);
+        let dx = noise::value(seed, x / Self::WARP_SCALE, y / Self::WARP_SCALE) - 0.5;
+        let dy = noise::value(
+            seed.wrapping_add(1),
+            x / Self::WARP_SCALE,
+            y / Self::WARP_SCALE,
+        ) - 0.5;
+        (x + dx * 2.0 * Self::WARP, y + dy * 2.0 * Self::WARP)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Feature {
+    FallenTree,
+    Clearing,
+    Boulder,
+    LoneTree,
+    Stream,
+    Reeds,
+    Jetty,
+    Cairn,
+    DeadTree,
+    Footbridge,
+}
+
+impl Display for Feature {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        let sentence = match self {
+            Self::FallenTree => "a fallen tree lies across the ground nearby.",
+            Self::Clearing => "the trees open up into a small clearing.",
+            Self::Boulder => "a large boulder sits half buried in the ground.",
+            Self::LoneTree => "a lone tree stands nearby.",
+            Self::Stream => "a stream trickles past.",
+            Self::Reeds => "tall reeds grow thickly here.",
+            Self::Jetty => "an old wooden jetty reaches out over the water.",
+            Self::Cairn => "someone has piled stones into a cairn.",
+            Self::DeadTree => "a dead tree stands bleached and bare.",
+            Self::Footbridge => "a narrow footbridge crosses the river.",
+        };
+        write!(f, "{}", sentence)
+    }
+}